@@ -2,6 +2,9 @@ use std::path::PathBuf;
 
 use clap::Subcommand;
 
+use super::create::ConflictPolicy;
+use crate::utils::Member;
+
 #[derive(Subcommand)]
 pub enum Commands {
     /// Create a new project from a template
@@ -15,12 +18,126 @@ pub enum Commands {
         /// The installation path of the project
         #[arg(short, long)]
         path: Option<PathBuf>,
+        /// How to handle files that already exist in the project directory
+        #[arg(long, value_enum, default_value_t = ConflictPolicy::Abort)]
+        on_conflict: ConflictPolicy,
+        /// Overwrite existing files (same as `--on-conflict overwrite`)
+        #[arg(short, long, conflicts_with = "on_conflict")]
+        force: bool,
+        /// Skip the pre-generate and post-generate hooks of the template
+        #[arg(long)]
+        no_hooks: bool,
+        /// Initialize a git repository with a .gitignore file and an initial commit
+        #[arg(long)]
+        git: bool,
+        /// Additional .gitignore templates to merge into the .gitignore file (e.g. JetBrains,macOS)
+        #[arg(long, value_delimiter = ',')]
+        gitignore: Vec<String>,
+        /// The SPDX identifier of the license to add (e.g. MIT, Apache-2.0)
+        #[arg(long)]
+        license: Option<String>,
+        /// The copyright holder of the license
+        #[arg(long)]
+        author: Option<String>,
+        /// A short description of the project for the README
+        #[arg(short, long)]
+        description: Option<String>,
+        /// Create a project inside the workspace, can be repeated (e.g. --member go:backend)
+        #[arg(long = "member", value_name = "LANGUAGE:NAME")]
+        members: Vec<Member>,
+        /// Run the start command or the given task in the new project after creating it
+        #[arg(long, value_name = "TASK")]
+        run: Option<Option<String>>,
+    },
+    /// Add a component to an existing project with a generator of its template
+    Generate {
+        /// The generator to run (e.g. module, package, page)
+        generator: Option<String>,
+        /// The name of the component
+        name: Option<String>,
+        /// The programming language of the project, detected if omitted
+        #[arg(short, long)]
+        language: Option<String>,
+        /// The root directory of the project
+        #[arg(short, long)]
+        path: Option<PathBuf>,
+        /// Overwrite files that already exist
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Merge the changes of the template into a project generated from it
+    Update {
+        /// The root directory of the project
+        #[arg(short, long)]
+        path: Option<PathBuf>,
+    },
+    /// Show how a generated project differs from its template
+    Diff {
+        /// The root directory of the project
+        #[arg(short, long)]
+        path: Option<PathBuf>,
+        /// The template of the project if it has no manifest, detected if omitted
+        #[arg(short, long)]
+        language: Option<String>,
+        /// Override a recorded answer (e.g. --var project_name=demo)
+        #[arg(long = "var", value_name = "NAME=VALUE")]
+        vars: Vec<String>,
+        /// Only show the changed lines per file
+        #[arg(long, conflicts_with = "json")]
+        stat: bool,
+        /// Print the differences as JSON, same as `--output json`
+        #[arg(long)]
+        json: bool,
+    },
+    /// Run a task of the project, the tasks are listed if no task is given
+    Run {
+        /// The task to run (e.g. build, test, run)
+        task: Option<String>,
+        /// The directory to search the task file from
+        #[arg(short, long)]
+        path: Option<PathBuf>,
+        /// Additional arguments for the command of the task
+        #[arg(last = true)]
+        args: Vec<String>,
     },
     /// Manage the available templates
     Templates {
         #[command(subcommand)]
         subcommand: TemplatesCommand,
     },
+    /// Manage the bundled and cached .gitignore templates
+    Gitignore {
+        #[command(subcommand)]
+        subcommand: GitignoreCommand,
+    },
+}
+
+impl Commands {
+    /// The name of the command as reported in the JSON output
+    ///
+    /// # Returns
+    ///
+    /// * `&'static str` - The name of the command (e.g. create, templates list)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Commands::Create { .. } => "create",
+            Commands::Generate { .. } => "generate",
+            Commands::Update { .. } => "update",
+            Commands::Diff { .. } => "diff",
+            Commands::Run { .. } => "run",
+            Commands::Templates { subcommand } => match subcommand {
+                TemplatesCommand::List { .. } => "templates list",
+                TemplatesCommand::Add { .. } => "templates add",
+                TemplatesCommand::Remove { .. } => "templates remove",
+                TemplatesCommand::Update { .. } => "templates update",
+                TemplatesCommand::Install { .. } => "templates install",
+            },
+            Commands::Gitignore { subcommand } => match subcommand {
+                GitignoreCommand::List => "gitignore list",
+                GitignoreCommand::Update { .. } => "gitignore update",
+            },
+        }
+    }
 }
 
 #[derive(Subcommand)]
@@ -36,6 +153,12 @@ pub enum TemplatesCommand {
         /// The programming language of the template
         #[arg(short, long)]
         language: Option<String>,
+        /// The command to start a project of the template
+        #[arg(long)]
+        start_command: Option<String>,
+        /// The directories of the template, comma separated (e.g. src,assets)
+        #[arg(long)]
+        directories: Option<String>,
     },
     /// Remove a template
     Remove {
@@ -49,4 +172,23 @@ pub enum TemplatesCommand {
         #[arg(short, long)]
         language: Option<String>,
     },
+    /// Install or upgrade the templates of a git repository
+    Install {
+        /// The repository as `<git-url-or-path>[#ref][:subdir]`
+        source: String,
+        /// Install templates with hooks without asking, `--yes` alone does not trust hooks
+        #[arg(long)]
+        trust_hooks: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum GitignoreCommand {
+    /// List the available .gitignore templates
+    List,
+    /// Fetch the latest .gitignore templates from GitHub into the cache
+    Update {
+        /// The .gitignore templates to update, all available ones if omitted
+        names: Vec<String>,
+    },
 }
@@ -1,14 +1,76 @@
-use colored::*;
-use std::path::{Path, PathBuf};
+use clap::ValueEnum;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
 
-use inquire::{Confirm, Select, Text};
+use inquire::{validator::Validation, Select, Text};
+use serde::Serialize;
 
-use crate::utils::{Config, Template};
+use crate::utils::{
+    configured_user_name, current_year, find_license, init_repository, license_ids,
+    license_uses_author, render_license, suggest_name, validate_name, Config, EditorConfig,
+    GitignoreSource, Hook, HookReport, Manifest, Member, Output, ParentBuild, Prompter, Staging,
+    Style, TaskRun, Template, MANIFEST_FILE, TASK_FILE,
+};
+
+/// How to handle files of the template that already exist in the target directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ConflictPolicy {
+    /// Abort before anything is written
+    #[default]
+    Abort,
+    /// Overwrite the existing files
+    Overwrite,
+    /// Keep the existing files and skip them
+    Skip,
+    /// Show a diff and ask for every conflicting file
+    Ask,
+}
 
 pub struct CreateCommand {
     pub language: String,
     pub name: String,
     pub path: PathBuf,
+    pub conflict_policy: ConflictPolicy,
+    pub prompter: Prompter,
+    pub output: Output,
+    pub run_hooks: bool,
+    pub init_git: bool,
+    pub gitignore: Vec<String>,
+    pub license: Option<String>,
+    pub author: Option<String>,
+    pub description: Option<String>,
+    pub members: Vec<Member>,
+    /// The task to run in the new project, `Some(None)` runs the start command
+    pub run: Option<Option<String>>,
+}
+
+/// A file written next to the files of the template (e.g. README, LICENSE)
+struct ExtraFile {
+    path: PathBuf,
+    content: String,
+    message: String,
+}
+
+/// The result of creating a project
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatedProject {
+    pub project: String,
+    pub language: String,
+    pub template: String,
+    pub template_version: Option<String>,
+    pub path: PathBuf,
+    pub license: Option<String>,
+    pub files: Vec<PathBuf>,
+    pub hooks: Vec<HookReport>,
+    pub next_steps: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_build: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<CreatedProject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run: Option<TaskRun>,
 }
 
 impl CreateCommand {
@@ -27,8 +89,10 @@ impl CreateCommand {
     /// # Errors
     ///
     /// * If the user input is invalid
+    /// * If a required value is missing in non-interactive mode
     /// * If the project cannot be created
-    /// * If the .gitignore file cannot be fetched
+    /// * If a required pre-generate or post-generate hook fails
+    /// * If a member of the workspace cannot be created
     pub fn run_create(
         &mut self,
         language: Option<String>,
@@ -36,184 +100,1069 @@ impl CreateCommand {
         path: Option<PathBuf>,
         config: &Config,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        println!("{}", "Creating Project...".bright_green().bold());
+        self.output
+            .line(self.output.paint("Creating Project...", Style::Success));
 
         let programming_languages = config.languages.clone();
 
-        if let Some(language) = language {
-            self.language = self.capitalize(language.as_str());
-        } else {
-            let selected_language = Select::new(
+        let language = match language {
+            None if !self.members.is_empty() => Some("workspace".to_string()),
+            language => language,
+        };
+        let language = self.prompter.require(language, "--language", || {
+            Select::new(
                 "Select one of these programming languages!",
                 programming_languages,
             )
-            .prompt()?;
+            .prompt()
+        })?;
+        self.language = self.capitalize(language.as_str());
 
-            self.language = self.capitalize(selected_language.as_str()).to_string();
-        }
-
-        if let Some(name) = name {
-            self.name = name;
-        } else {
-            let project_name = Text::new("Enter the name of the project")
+        let name_language = self.language.clone();
+        let name = self.prompter.require(name, "--name", || {
+            Text::new("Enter the name of the project")
                 .with_help_message("This name is for your project folder name")
-                .prompt()?;
-            self.name = project_name;
-        }
+                .with_validator(move |input: &str| {
+                    Ok(match validate_name(&name_language, input) {
+                        Ok(_) => Validation::Valid,
+                        Err(reason) => Validation::Invalid(
+                            format!("{}. Try `{}`", reason, suggest_name(&name_language, input))
+                                .into(),
+                        ),
+                    })
+                })
+                .prompt()
+        })?;
+        self.name = self.checked_name(name)?;
 
         if let Some(path) = path {
             self.path = path;
-        } else if !Confirm::new("Use current directory? [y/n]").prompt()? {
-            let project_path = Text::new("Enter the path of the project")
-                .with_help_message("This path is for your project folder path")
-                .prompt()?;
+        } else if !self
+            .prompter
+            .confirm("Use current directory? [y/n]", true)?
+        {
+            let project_path = self.prompter.require(None, "--path", || {
+                Text::new("Enter the path of the project")
+                    .with_help_message("This path is for your project folder path")
+                    .prompt()
+            })?;
             self.path = PathBuf::from(project_path);
         }
 
-        self.create_project()?;
+        if self.description.is_none() && self.prompter.interactive {
+            let description = Text::new("Enter a short description of the project")
+                .with_help_message("The description is used in the README, leave it empty to skip")
+                .prompt()?;
+            self.description = Some(description);
+        }
 
-        println!(
-            "\n{}",
-            "Project successfully created! 🎉".bright_green().bold()
-        );
-        println!("\n{}", "Summary:".bright_yellow().bold());
-        println!("   {} Language: {}", "→".bright_blue(), self.language);
-        println!("   {} Project: {}", "→".bright_blue(), self.name);
-        println!(
-            "   {} Location: {}",
-            "→".bright_blue(),
-            self.path.join(&self.name).display()
-        );
-        println!("\n{}", "Next steps:".bright_yellow().bold());
-        println!(
-            "   {} cd {}",
-            "→".bright_blue(),
-            self.path.join(&self.name).to_str().unwrap()
-        );
-        println!(
-            "   {} {}",
-            "→".bright_blue(),
-            Template::load_template(self.language.clone())?.start_command
-        );
+        self.select_license(config)?;
 
-        Ok(())
+        let mut template = Template::load_template(self.language.clone(), config)?;
+        template.members.extend(self.members.clone());
+        let run = self
+            .run
+            .as_ref()
+            .map(|task| self.run_command(&template, task.as_deref()))
+            .transpose()?;
+        let members = self.prepare_members(&template.members, config)?;
+
+        let project_path = self.path.join(&self.name);
+        let existed = project_path.exists();
+        let mut project = self.generate(&template, config)?;
+        project.members = match self.create_members(members, config) {
+            Ok(members) => members,
+            Err(err) => {
+                if !existed && std::fs::remove_dir_all(&project_path).is_ok() {
+                    self.output.failure(&format!(
+                        "Removed the incomplete workspace {}",
+                        project_path.display()
+                    ));
+                }
+                return Err(err);
+            }
+        };
+
+        if self.init_git && !template.members.is_empty() {
+            self.init_git_repository(&project_path, &project_path, config)?;
+        }
+
+        self.output.done("Project successfully created!");
+        self.output.heading("Summary:");
+        self.output.item(format!("Language: {}", self.language));
+        self.output.item(format!("Project: {}", self.name));
+        if let Some(license) = &self.license {
+            self.output.item(format!("License: {}", license));
+        }
+        self.output
+            .item(format!("Location: {}", project_path.display()));
+        if !project.members.is_empty() {
+            self.output.heading("Members:");
+            for member in &project.members {
+                self.output.item(format!(
+                    "{} {}: {}",
+                    member.language,
+                    member.project,
+                    member.path.display()
+                ));
+            }
+        }
+
+        let hook_reports: Vec<&HookReport> = project
+            .hooks
+            .iter()
+            .chain(project.members.iter().flat_map(|member| &member.hooks))
+            .collect();
+        if !hook_reports.is_empty() {
+            self.output.heading("Hooks:");
+            for report in &hook_reports {
+                self.output.item(match &report.error {
+                    None => format!(
+                        "{} {}",
+                        report.command,
+                        self.output.paint("(ok)", Style::Success)
+                    ),
+                    Some(error) if report.optional => format!(
+                        "{} {}",
+                        report.command,
+                        self.output
+                            .paint(&format!("(optional, {})", error), Style::Warning)
+                    ),
+                    Some(error) => format!(
+                        "{} {}",
+                        report.command,
+                        self.output.paint(&format!("({})", error), Style::Error)
+                    ),
+                });
+            }
+        }
+
+        self.output.heading("Next steps:");
+        for step in &project.next_steps {
+            self.output.item(step);
+        }
+        for member in &project.members {
+            self.output.item(member.next_steps.join(" && "));
+        }
+
+        if let Some(report) = hook_reports
+            .iter()
+            .find(|report| report.error.is_some() && !report.optional)
+        {
+            return Err(format!("Required hook `{}` failed", report.command).into());
+        }
+
+        if let Some((task, command)) = run {
+            self.output.step(&format!("Running {}: {}", task, command));
+            project.run = Some(TaskRun::execute(
+                &task,
+                &command,
+                &project_path,
+                self.output.is_json(),
+            )?);
+        }
+
+        self.output.result("create", &project)?;
+        match &project.run {
+            Some(run) => Ok(run.check()?),
+            None => Ok(()),
+        }
+    }
+
+    /// Find the command to run in the new project
+    ///
+    /// Without a task name the `run` task of the template is used, or its start command if it
+    /// has no such task.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template of the project
+    /// * `task` - The name of the task to run
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(String, String), Box<dyn std::error::Error>>` - The name and the command line of the task
+    ///
+    /// # Errors
+    ///
+    /// * If the template has no task with the given name
+    /// * If the template has neither a `run` task nor a start command
+    fn run_command(
+        &self,
+        template: &Template,
+        task: Option<&str>,
+    ) -> Result<(String, String), Box<dyn std::error::Error>> {
+        let variables = self.variables();
+        let render = |command: &str| template.render(vec![command.to_string()], &variables);
+
+        match task {
+            Some(task) => {
+                let command = template.tasks.get(task).ok_or_else(|| {
+                    format!(
+                        "The {} template has no task {}, available tasks: {}",
+                        self.language,
+                        task,
+                        template
+                            .tasks
+                            .keys()
+                            .cloned()
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                })?;
+                Ok((task.to_string(), render(command)?))
+            }
+            None => match template.tasks.get("run") {
+                Some(command) => Ok(("run".to_string(), render(command)?)),
+                None if !template.start_command.is_empty() => {
+                    Ok(("start".to_string(), render(&template.start_command)?))
+                }
+                None => Err(format!("The {} template has no start command", self.language).into()),
+            },
+        }
+    }
+
+    /// Generate the project from the template including its hooks
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template of the project
+    /// * `config` - The configuration object
+    ///
+    /// # Returns
+    ///
+    /// * `Result<CreatedProject, Box<dyn std::error::Error>>` - The created project
+    ///
+    /// # Errors
+    ///
+    /// * If files of the project already exist and the conflict policy is abort
+    /// * If a required pre-generate hook fails
+    /// * If the project cannot be created
+    fn generate(
+        &self,
+        template: &Template,
+        config: &Config,
+    ) -> Result<CreatedProject, Box<dyn std::error::Error>> {
+        let add_gitignore = self.init_git
+            || self
+                .prompter
+                .confirm("Do you want to add a .gitignore file? [y/n]", true)?;
+        self.check_conflicts(template, config, add_gitignore)?;
+
+        if self.run_hooks {
+            self.run_pre_generate_hooks(template)?;
+        }
+
+        let files = self.create_project(template, config, add_gitignore)?;
+        // The project is already on disk, so a failed registration must not skip the hooks
+        let parent_build = self.register_in_parent_build().unwrap_or_else(|error| {
+            self.output.warning(&format!(
+                "Project could not be registered in the parent build ({})",
+                error
+            ));
+            None
+        });
+
+        let hooks = if self.run_hooks {
+            self.run_post_generate_hooks(template)
+        } else {
+            vec![]
+        };
+
+        let path = self.path.join(&self.name);
+        let mut next_steps = vec![format!("cd {}", path.display())];
+        if !template.start_command.is_empty() {
+            next_steps
+                .push(template.render(vec![template.start_command.clone()], &self.variables())?);
+        }
+
+        Ok(CreatedProject {
+            project: self.name.clone(),
+            language: self.language.clone(),
+            template: self.language.to_lowercase(),
+            template_version: template.version.clone(),
+            path,
+            license: self.license.clone(),
+            files,
+            hooks,
+            next_steps,
+            parent_build,
+            members: vec![],
+            run: None,
+        })
+    }
+
+    /// Register the project in the enclosing parent build if the user agrees
+    ///
+    /// Parent builds are Cargo workspaces, go.work files and Maven parent poms.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<PathBuf>, Box<dyn std::error::Error>>` - The manifest of the parent build the project is registered in
+    ///
+    /// # Errors
+    ///
+    /// * If the manifest of the parent build cannot be edited
+    fn register_in_parent_build(&self) -> Result<Option<PathBuf>, Box<dyn std::error::Error>> {
+        let project_path = self.path.join(&self.name).canonicalize()?;
+        let Some(parent) = ParentBuild::detect(&self.language, &project_path) else {
+            return Ok(None);
+        };
+
+        if !self.prompter.confirm(
+            &format!(
+                "Register {} in the {} at {}? [y/n]",
+                self.name,
+                parent.description(),
+                parent.manifest().display()
+            ),
+            false,
+        )? {
+            self.output.skipped(&format!(
+                "Not registered in the {} at {}",
+                parent.description(),
+                parent.manifest().display()
+            ));
+            return Ok(None);
+        }
+
+        if parent.register(&project_path)? {
+            self.output.success(&format!(
+                "Registered in the {} at {}",
+                parent.description(),
+                parent.manifest().display()
+            ));
+        } else {
+            self.output.success(&format!(
+                "Already a member of the {} at {}",
+                parent.description(),
+                parent.manifest().display()
+            ));
+        }
+
+        Ok(Some(parent.manifest().to_path_buf()))
+    }
+
+    /// Validate the members of a workspace and load their templates
+    ///
+    /// This runs before anything is generated, so an invalid member does not leave a
+    /// half-built workspace behind. The members share the options of the workspace, but get
+    /// no git repository and no description of their own.
+    ///
+    /// # Arguments
+    ///
+    /// * `members` - The members of the workspace
+    /// * `config` - The configuration object
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<(CreateCommand, Template)>, Box<dyn std::error::Error>>` - The create commands and templates of the members
+    ///
+    /// # Errors
+    ///
+    /// * If the name of a member is invalid
+    /// * If the template of a member cannot be loaded or is a workspace itself
+    fn prepare_members(
+        &self,
+        members: &[Member],
+        config: &Config,
+    ) -> Result<Vec<(CreateCommand, Template)>, Box<dyn std::error::Error>> {
+        let mut prepared = vec![];
+
+        for member in members {
+            let mut command = CreateCommand {
+                language: self.capitalize(&member.template),
+                path: self
+                    .path
+                    .join(&self.name)
+                    .join(member.path.clone().unwrap_or_default()),
+                conflict_policy: self.conflict_policy,
+                prompter: self.prompter,
+                output: self.output.clone(),
+                run_hooks: self.run_hooks,
+                init_git: false,
+                gitignore: vec![],
+                license: self.license.clone(),
+                author: self.author.clone(),
+                description: None,
+                ..Default::default()
+            };
+            command.name = command.checked_name(member.name.clone())?;
+
+            let template = Template::load_template(command.language.clone(), config)?;
+            if !template.members.is_empty() {
+                return Err(format!(
+                    "The member {} uses the workspace template {}, workspaces cannot be nested",
+                    member.name, member.template
+                )
+                .into());
+            }
+
+            prepared.push((command, template));
+        }
+
+        Ok(prepared)
+    }
+
+    /// Create the members of a workspace inside the project directory
+    ///
+    /// # Arguments
+    ///
+    /// * `members` - The create commands and templates of the members
+    /// * `config` - The configuration object
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<CreatedProject>, Box<dyn std::error::Error>>` - The created members
+    ///
+    /// # Errors
+    ///
+    /// * If a member cannot be created
+    fn create_members(
+        &self,
+        members: Vec<(CreateCommand, Template)>,
+        config: &Config,
+    ) -> Result<Vec<CreatedProject>, Box<dyn std::error::Error>> {
+        let mut created = vec![];
+
+        for (command, template) in members {
+            self.output.step(&format!(
+                "Creating {} project {}...",
+                command.language, command.name
+            ));
+            created.push(command.generate(&template, config)?);
+        }
+
+        Ok(created)
     }
 
     /// Create a project with the given options
     ///
+    /// # Arguments
+    ///
+    /// * `template` - The template of the project
+    /// * `config` - The configuration object
+    /// * `add_gitignore` - Whether a .gitignore file is added to the project
+    ///
     /// # Returns
     ///
-    /// * `Result<(), Box<dyn std::error::Error>>` - The result of the create project
+    /// * `Result<Vec<PathBuf>, Box<dyn std::error::Error>>` - The files generated for the project
     ///
     /// # Errors
     ///
     /// * If the project cannot be created
-    /// * If the .gitignore file cannot be fetched
-    fn create_project(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let template = Template::load_template(self.language.clone())?;
-
-        println!(
-            "└─ {} {}",
-            "►".bright_blue(),
-            "Creating project structure...".bright_white()
-        );
+    /// * If the git repository cannot be initialized
+    fn create_project(
+        &self,
+        template: &Template,
+        config: &Config,
+        add_gitignore: bool,
+    ) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+        self.output.step("Creating project structure...");
         let project_path = self.path.join(&self.name);
-        std::fs::create_dir_all(&project_path)?;
+        let editorconfig = template.editorconfig(&project_path, &self.variables())?;
+
+        let staging = Staging::new(&project_path)?;
 
         for folder in template.structure.directories.clone() {
-            let folder_path = project_path.join(folder);
+            let folder_path = staging.path().join(folder);
             std::fs::create_dir_all(&folder_path)?;
         }
-        println!(
-            "   └─ {} {}",
-            "✓".bright_green(),
-            "Project structure created!".green()
-        );
+        self.output.success("Project structure created!");
+
+        let mut generated =
+            self.create_files(template, &project_path, staging.path(), &editorconfig)?;
 
-        self.create_files(&template, &project_path)?;
-
-        if Confirm::new("Do you want to add a .gitignore file? [y/n]").prompt()? {
-            match self.fetch_gitignore(self.language.clone()) {
-                Ok(_) => println!(
-                    "   └─ {} {}",
-                    "✓".bright_green(),
-                    ".gitignore file added!".green()
-                ),
-                Err(_) => println!(
-                    "   └─ {} {}",
-                    "✗".bright_red(),
-                    ".gitignore file could not be added".red()
-                ),
+        let extra_files = self.extra_files(template, config, add_gitignore)?;
+        for file in extra_files {
+            let content =
+                template.format_file(&file.path, &file.content, &project_path, &editorconfig);
+            if self.should_write(&project_path.join(&file.path), &content)? {
+                self.write_file(staging.path(), &project_path, &file.path, &content)?;
             }
+            self.output.success(&file.message);
+            generated.push((file.path, content));
+        }
+
+        if !add_gitignore {
+            self.output.failure(".gitignore file not added");
+        }
+
+        let mut manifest = Manifest::new(
+            &self.language.to_lowercase(),
+            template,
+            self.answers(),
+            staging.path(),
+        )?;
+        // Files that were not written (e.g. because they already exist with the same content)
+        // are still generated by the template
+        for (path, content) in generated {
+            manifest
+                .files
+                .entry(path)
+                .or_insert_with(|| Manifest::checksum(content.as_bytes()));
+        }
+        manifest.save(staging.path())?;
+        self.output.detail(&format!(
+            "Wrote {}",
+            project_path.join(MANIFEST_FILE).display()
+        ));
+
+        if self.init_git && template.members.is_empty() {
+            self.init_git_repository(staging.path(), &project_path, config)?;
+        }
+
+        staging.commit()?;
+
+        Ok(manifest.files.into_keys().collect())
+    }
+
+    /// Initialize the git repository of the project with an initial commit
+    ///
+    /// # Arguments
+    ///
+    /// * `repository_path` - The directory to initialize the repository in
+    /// * `project_path` - The final path of the project, used to detect an existing repository
+    /// * `config` - The configuration object
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Box<dyn std::error::Error>>` - The result of the initialization
+    ///
+    /// # Errors
+    ///
+    /// * If the git repository cannot be initialized
+    fn init_git_repository(
+        &self,
+        repository_path: &Path,
+        project_path: &Path,
+        config: &Config,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if project_path.join(".git").exists() {
+            self.output
+                .warning("Git repository already exists, skipped initialization");
         } else {
-            println!(
-                "   └─ {} {}",
-                "✗".bright_red(),
-                ".gitignore file not added".red()
-            );
+            init_repository(repository_path, &config.git)?;
+            self.output.success("Git repository initialized!");
         }
 
         Ok(())
     }
 
-    /// Fetch the .gitignore file for the given language
+    /// Create the files for the project
+    ///
+    /// The content of the files is normalized according to the formatting of the template and
+    /// the `.editorconfig` settings.
     ///
     /// # Arguments
     ///
-    /// * `language` - The programming language of the project
+    /// * `template` - The template of the project
+    /// * `project_path` - The final path of the project, used to detect existing files
+    /// * `staging_path` - The path of the staging directory the files are written to
+    /// * `editorconfig` - The `.editorconfig` settings of the project
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<(PathBuf, String)>, Box<dyn std::error::Error>>` - The generated files, including the ones that were not written
+    fn create_files(
+        &self,
+        template: &Template,
+        project_path: &Path,
+        staging_path: &Path,
+        editorconfig: &EditorConfig,
+    ) -> Result<Vec<(PathBuf, String)>, Box<dyn std::error::Error>> {
+        self.output.step("Creating project files...");
+        let mut generated = vec![];
+        for file in template.structure.files.clone() {
+            let content = template.render(file.content.clone(), &self.variables())?;
+            let content = template.format_file(&file.path, &content, project_path, editorconfig);
+            if self.should_write(&project_path.join(&file.path), &content)? {
+                self.write_file(staging_path, project_path, &file.path, &content)?;
+            }
+            generated.push((file.path, content));
+        }
+        self.output.success("Project files created!");
+
+        Ok(generated)
+    }
+
+    /// Write a file of the project into the staging directory
+    ///
+    /// # Arguments
+    ///
+    /// * `staging_path` - The path of the staging directory
+    /// * `project_path` - The final path of the project, reported in verbose mode
+    /// * `path` - The path of the file relative to the project
+    /// * `content` - The content of the file
     ///
     /// # Returns
     ///
-    /// * `Result<String, Box<dyn std::error::Error>>` - The result of the fetch gitignore
-    fn fetch_gitignore(&self, language: String) -> Result<String, Box<dyn std::error::Error>> {
-        let formatted_language = if language == "C++" {
-            language.replace("+", "%2B")
+    /// * `Result<(), Box<dyn std::error::Error>>` - The result of writing the file
+    ///
+    /// # Errors
+    ///
+    /// * If the file or its parent directories cannot be created
+    fn write_file(
+        &self,
+        staging_path: &Path,
+        project_path: &Path,
+        path: &Path,
+        content: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let file_path = staging_path.join(path);
+        if let Some(parent) = file_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&file_path, content)?;
+        self.output
+            .detail(&format!("Wrote {}", project_path.join(path).display()));
+
+        Ok(())
+    }
+
+    /// Run the pre-generate hooks of the template before anything is written
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template of the project
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Box<dyn std::error::Error>>` - The result of the pre-generate hooks
+    ///
+    /// # Errors
+    ///
+    /// * If a required pre-generate hook fails
+    fn run_pre_generate_hooks(
+        &self,
+        template: &Template,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if template.hooks.pre_generate.is_empty() {
+            return Ok(());
+        }
+
+        self.output.step("Running pre-generate hooks...");
+
+        let base_dir = if self.path.exists() {
+            self.path.clone()
         } else {
-            language
+            std::env::current_dir()?
         };
+        let variables = self.hook_environment();
 
-        let url = format!(
-            "https://raw.githubusercontent.com/github/gitignore/refs/heads/main/{}.gitignore",
-            formatted_language
-        );
+        for hook in &template.hooks.pre_generate {
+            match hook.check(&base_dir, &variables) {
+                Ok(_) => self.output.success(&hook.command),
+                Err(error) if hook.optional => self
+                    .output
+                    .warning(&format!("{} ({})", hook.command, error)),
+                Err(error) => {
+                    return Err(
+                        format!("Generation aborted by `{}`: {}", hook.command, error).into(),
+                    )
+                }
+            }
+        }
 
-        let gitignore = ureq::get(&url).call()?.into_string()?;
+        Ok(())
+    }
 
-        Ok(gitignore)
+    /// Run the post-generate hooks of the template in the created project
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template of the project
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<HookReport>` - The reports of the hooks that have been run
+    fn run_post_generate_hooks(&self, template: &Template) -> Vec<HookReport> {
+        if template.hooks.post_generate.is_empty() {
+            return vec![];
+        }
+
+        self.output.step("Running post-generate hooks...");
+
+        Hook::run_all(
+            &template.hooks.post_generate,
+            &self.path.join(&self.name),
+            &self.hook_environment(),
+            !self.output.streams_commands(),
+        )
     }
 
-    /// Create the files for the project
+    /// Check the project name against the naming rules of the language
+    ///
+    /// If the name is invalid, the user is offered the suggested name instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the project
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, Box<dyn std::error::Error>>` - The valid project name
+    ///
+    /// # Errors
+    ///
+    /// * If the name is invalid and the suggested name is not accepted
+    fn checked_name(&self, name: String) -> Result<String, Box<dyn std::error::Error>> {
+        let Err(reason) = validate_name(&self.language, &name) else {
+            return Ok(name);
+        };
+
+        let suggestion = suggest_name(&self.language, &name);
+        if self.prompter.interactive
+            && self.prompter.confirm(
+                &format!("{}. Use `{}` instead? [y/n]", reason, suggestion),
+                true,
+            )?
+        {
+            return Ok(suggestion);
+        }
+
+        Err(format!(
+            "Invalid {} project name `{}`: {}. Suggested name: `{}`",
+            self.language, name, reason, suggestion
+        )
+        .into())
+    }
+
+    /// Select the license of the project and the copyright holder
+    ///
+    /// The user is asked for a license if none is given and the copyright holder is taken
+    /// from the configuration or git if it is not given. The copyright holder is only required
+    /// if the license text names it.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration object
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Box<dyn std::error::Error>>` - The result of the license selection
+    ///
+    /// # Errors
+    ///
+    /// * If the license is not bundled
+    /// * If the user input is invalid
+    fn select_license(&mut self, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(license) = &self.license {
+            self.license = Some(find_license(license)?);
+        } else if self.prompter.interactive {
+            let mut options = vec!["None".to_string()];
+            options.extend(license_ids());
+
+            let selected = Select::new("Select a license for the project", options).prompt()?;
+            if selected != "None" {
+                self.license = Some(selected);
+            }
+        }
+
+        if let (Some(license), None) = (&self.license, &self.author) {
+            let author = config
+                .author
+                .clone()
+                .or_else(|| config.git.author_name.clone())
+                .or_else(configured_user_name);
+            self.author = if license_uses_author(license)? {
+                Some(self.prompter.require(author, "--author", || {
+                    Text::new("Enter the name of the copyright holder").prompt()
+                })?)
+            } else {
+                author
+            };
+        }
+
+        Ok(())
+    }
+
+    /// The variables of the project that can be used in the template files
+    ///
+    /// # Returns
+    ///
+    /// * `HashMap<String, String>` - The variables of the project
+    fn variables(&self) -> HashMap<String, String> {
+        HashMap::from([
+            ("project_name".to_string(), self.name.clone()),
+            ("language".to_string(), self.language.clone()),
+            (
+                "description".to_string(),
+                self.description.clone().unwrap_or_default(),
+            ),
+            (
+                "project_path".to_string(),
+                self.path.join(&self.name).display().to_string(),
+            ),
+            (
+                "license".to_string(),
+                self.license.clone().unwrap_or_default(),
+            ),
+            (
+                "author".to_string(),
+                self.author.clone().unwrap_or_default(),
+            ),
+            ("year".to_string(), current_year().to_string()),
+        ])
+    }
+
+    /// The answers that are recorded in the manifest of the project
+    ///
+    /// The answers are the variables of the project without the machine specific path,
+    /// together with the chosen .gitignore templates.
+    ///
+    /// # Returns
+    ///
+    /// * `BTreeMap<String, String>` - The answers of the project
+    fn answers(&self) -> BTreeMap<String, String> {
+        let mut answers: BTreeMap<String, String> = self
+            .variables()
+            .into_iter()
+            .filter(|(name, _)| name != "project_path")
+            .collect();
+        answers.insert("gitignore".to_string(), self.gitignore.join(","));
+
+        answers
+    }
+
+    /// The variables of the project that are passed to the hooks as environment variables
+    ///
+    /// # Returns
+    ///
+    /// * `HashMap<String, String>` - The variables prefixed with `SCAFFER_` in upper case
+    fn hook_environment(&self) -> HashMap<String, String> {
+        self.variables()
+            .into_iter()
+            .map(|(name, value)| (format!("SCAFFER_{}", name.to_uppercase()), value))
+            .collect()
+    }
+
+    /// The files written next to the files of the template
+    ///
+    /// These are the README (unless the template ships one), the task file, the LICENSE and
+    /// the .gitignore, each with the message reported after it has been written.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template of the project
+    /// * `config` - The configuration object
+    /// * `add_gitignore` - Whether a .gitignore file is added to the project
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<ExtraFile>, Box<dyn std::error::Error>>` - The unformatted files
+    ///
+    /// # Errors
+    ///
+    /// * If a file cannot be rendered
+    /// * If one of the gitignore templates cannot be found
+    fn extra_files(
+        &self,
+        template: &Template,
+        config: &Config,
+        add_gitignore: bool,
+    ) -> Result<Vec<ExtraFile>, Box<dyn std::error::Error>> {
+        let mut files = vec![];
+
+        if !template.has_readme() {
+            files.push(ExtraFile {
+                path: PathBuf::from("README.md"),
+                content: template.render_readme(&self.variables())?,
+                message: "README.md generated!".to_string(),
+            });
+        }
+
+        if let Some(tasks) = template.render_tasks(&self.variables())? {
+            files.push(ExtraFile {
+                path: PathBuf::from(TASK_FILE),
+                content: tasks,
+                message: format!("{} tasks added!", TASK_FILE),
+            });
+        }
+
+        if let Some(license) = &self.license {
+            files.push(ExtraFile {
+                path: PathBuf::from("LICENSE"),
+                content: render_license(
+                    license,
+                    self.author.as_deref().unwrap_or_default(),
+                    current_year(),
+                )?,
+                message: format!("{} license added!", license),
+            });
+        }
+
+        if add_gitignore {
+            files.push(ExtraFile {
+                path: PathBuf::from(".gitignore"),
+                content: GitignoreSource::from_config(config)?
+                    .merge(&template.gitignore_names(&self.language, &self.gitignore))?,
+                message: ".gitignore file added!".to_string(),
+            });
+        }
+
+        Ok(files)
+    }
+
+    /// Abort if files of the project already exist and the conflict policy is abort
+    ///
+    /// All generated files are checked before anything is run or written. The extra files are
+    /// rendered under every policy, so e.g. an unknown gitignore name fails before any hook runs.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template of the project
+    /// * `config` - The configuration object
+    /// * `add_gitignore` - Whether a .gitignore file is added to the project
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Box<dyn std::error::Error>>` - The result of the check
+    ///
+    /// # Errors
+    ///
+    /// * If an extra file cannot be rendered
+    /// * If files exist with a different content and the conflict policy is abort
+    fn check_conflicts(
+        &self,
+        template: &Template,
+        config: &Config,
+        add_gitignore: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let extra_files = self.extra_files(template, config, add_gitignore)?;
+        if self.conflict_policy != ConflictPolicy::Abort {
+            return Ok(());
+        }
+
+        let project_path = self.path.join(&self.name);
+        let editorconfig = template.editorconfig(&project_path, &self.variables())?;
+
+        let conflicts =
+            self.find_conflicts(template, &extra_files, &project_path, &editorconfig)?;
+        if conflicts.is_empty() {
+            return Ok(());
+        }
+
+        let conflicts = conflicts
+            .iter()
+            .map(|path| format!("  - {}", path.display()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        Err(format!(
+            "The following files already exist in {}:\n{}\nUse --force to overwrite or --on-conflict to choose another policy",
+            project_path.display(),
+            conflicts
+        )
+        .into())
+    }
+
+    /// Find the generated files that already exist with a different content
     ///
     /// # Arguments
     ///
     /// * `template` - The template of the project
+    /// * `extra_files` - The files written next to the files of the template
     /// * `project_path` - The path of the project
+    /// * `editorconfig` - The `.editorconfig` settings of the project
     ///
     /// # Returns
     ///
-    /// * `Result<(), Box<dyn std::error::Error>>` - The result of the create files
-    fn create_files(
+    /// * `Result<Vec<PathBuf>, Box<dyn std::error::Error>>` - The paths of the conflicting files
+    fn find_conflicts(
         &self,
         template: &Template,
+        extra_files: &[ExtraFile],
         project_path: &Path,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        println!(
-            "└─ {} {}",
-            "►".bright_blue(),
-            "Creating project files...".bright_white()
-        );
-        for file in template.structure.files.clone() {
-            let file_path = project_path.join(file.path);
-            let content = template.parse_project_name(self.name.clone(), file.content.clone())?;
-            std::fs::write(&file_path, content)?;
-        }
-        println!(
-            "   └─ {} {}",
-            "✓".bright_green(),
-            "Project files created!".green()
+        editorconfig: &EditorConfig,
+    ) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+        let mut files = vec![];
+        for file in &template.structure.files {
+            files.push((
+                file.path.clone(),
+                template.render(file.content.clone(), &self.variables())?,
+            ));
+        }
+        files.extend(
+            extra_files
+                .iter()
+                .map(|file| (file.path.clone(), file.content.clone())),
         );
 
-        Ok(())
+        let mut conflicts = vec![];
+        for (path, content) in files {
+            let file_path = project_path.join(&path);
+            if !file_path.exists() {
+                continue;
+            }
+            let content = template.format_file(&path, &content, project_path, editorconfig);
+            if std::fs::read_to_string(&file_path).ok().as_deref() != Some(content.as_str()) {
+                conflicts.push(file_path);
+            }
+        }
+
+        Ok(conflicts)
+    }
+
+    /// Decide whether the given file should be written according to the conflict policy
+    ///
+    /// # Arguments
+    ///
+    /// * `file_path` - The path of the file
+    /// * `content` - The new content of the file
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, Box<dyn std::error::Error>>` - Whether the file should be written
+    ///
+    /// # Errors
+    ///
+    /// * If the file exists with a different content and the conflict policy is abort
+    /// * If the user input is invalid
+    fn should_write(
+        &self,
+        file_path: &Path,
+        content: &str,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        if !file_path.exists() {
+            return Ok(true);
+        }
+
+        let existing = std::fs::read_to_string(file_path).unwrap_or_default();
+        if existing == content {
+            return Ok(false);
+        }
+
+        match self.conflict_policy {
+            ConflictPolicy::Abort => Err(format!(
+                "{} already exists. Use --force to overwrite or --on-conflict to choose another policy",
+                file_path.display()
+            )
+            .into()),
+            ConflictPolicy::Overwrite => Ok(true),
+            ConflictPolicy::Skip => {
+                self.output
+                    .skipped(&format!("Skipped existing file {}", file_path.display()));
+                Ok(false)
+            }
+            ConflictPolicy::Ask => {
+                self.output.line(format!(
+                    "\n{} {}",
+                    self.output.paint("File already exists:", Style::Heading),
+                    file_path.display()
+                ));
+                let patch = diffy::create_patch(&existing, content);
+                for line in patch.to_string().lines() {
+                    if line.starts_with('+') {
+                        self.output.line(self.output.paint(line, Style::Success));
+                    } else if line.starts_with('-') {
+                        self.output.line(self.output.paint(line, Style::Error));
+                    } else {
+                        self.output.line(line);
+                    }
+                }
+                self.prompter.confirm("Overwrite this file? [y/n]", false)
+            }
+        }
     }
 
     /// Capitalize the first letter of the given string
@@ -236,6 +1185,17 @@ impl Default for CreateCommand {
             language: String::new(),
             name: String::new(),
             path: std::env::current_dir().expect("Failed to use current directory"),
+            conflict_policy: ConflictPolicy::default(),
+            prompter: Prompter::default(),
+            output: Output::default(),
+            run_hooks: true,
+            init_git: false,
+            gitignore: vec![],
+            license: None,
+            author: None,
+            description: None,
+            members: vec![],
+            run: None,
         }
     }
 }
@@ -243,6 +1203,8 @@ impl Default for CreateCommand {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::{create_temp_dir, OutputFormat};
+    use std::str::FromStr;
 
     #[test]
     fn test_create_command_default() {
@@ -268,10 +1230,258 @@ mod tests {
     }
 
     #[test]
-    fn test_fetch_gitignore() {
-        let create_command = CreateCommand::default();
+    fn test_create_project_writes_gitignore() {
+        let dir = create_temp_dir("scaffer_test_create_gitignore").unwrap();
+        let gitignore_dir = dir.join("gitignore");
+        std::fs::create_dir_all(&gitignore_dir).unwrap();
+        std::fs::write(gitignore_dir.join("Rust.gitignore"), "/target\n").unwrap();
+        std::fs::create_dir_all(dir.join("demo")).unwrap();
+        std::fs::write(dir.join("demo/.gitignore"), "### Rust ###\n/target\n").unwrap();
+
+        let mut config = Config::new(Config::bundled_template_dir());
+        config.gitignore.local_dir = Some(gitignore_dir);
+        let template: Template = serde_json::from_str(
+            r#"{
+                "structure": {
+                    "directories": ["src"],
+                    "files": [{ "path": "src/main.rs", "content": ["fn main() {}"] }]
+                },
+                "start_command": "cargo run",
+                "gitignore": ["Rust"]
+            }"#,
+        )
+        .unwrap();
+
+        let create_command = CreateCommand {
+            language: "Rust".to_string(),
+            name: "demo".to_string(),
+            path: dir.clone(),
+            prompter: Prompter::new(true, false),
+            ..Default::default()
+        };
+        create_command
+            .create_project(&template, &config, true)
+            .unwrap();
+
+        let gitignore = std::fs::read_to_string(dir.join("demo/.gitignore")).unwrap();
+        assert_eq!(gitignore, "### Rust ###\n/target\n");
+        assert!(dir.join("demo/src/main.rs").exists());
+        assert!(dir.join("demo/README.md").exists());
+
+        let manifest = Manifest::load(&dir.join("demo")).unwrap();
+        assert_eq!(manifest.template, "rust");
+        assert_eq!(manifest.answers["project_name"], "demo");
+        assert!(manifest.files.contains_key(Path::new("src/main.rs")));
+        assert_eq!(
+            manifest.files[Path::new(".gitignore")],
+            Manifest::checksum(b"### Rust ###\n/target\n")
+        );
+
+        let create_command = CreateCommand {
+            gitignore: vec!["Rsut".to_string()],
+            ..create_command
+        };
+        assert_eq!(
+            create_command
+                .check_conflicts(&template, &config, true)
+                .unwrap_err()
+                .to_string(),
+            "No gitignore template found for Rsut"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_check_conflicts_covers_generated_files() {
+        let dir = create_temp_dir("scaffer_test_check_conflicts").unwrap();
+        std::fs::create_dir_all(dir.join("demo")).unwrap();
+        std::fs::write(dir.join("demo/README.md"), "# My notes\n").unwrap();
+        std::fs::write(dir.join("demo/LICENSE"), "All rights reserved\n").unwrap();
+
+        let config = Config::new(Config::bundled_template_dir());
+        let template: Template = serde_json::from_str(
+            r#"{
+                "structure": { "directories": [], "files": [] },
+                "start_command": "cargo run"
+            }"#,
+        )
+        .unwrap();
+
+        let mut create_command = CreateCommand {
+            language: "Rust".to_string(),
+            name: "demo".to_string(),
+            path: dir.clone(),
+            license: Some("MIT".to_string()),
+            ..Default::default()
+        };
+        let error = create_command
+            .check_conflicts(&template, &config, false)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("README.md"));
+        assert!(error.contains("LICENSE"));
+
+        create_command.conflict_policy = ConflictPolicy::Skip;
+        assert!(create_command
+            .check_conflicts(&template, &config, false)
+            .is_ok());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_failed_parent_build_registration_is_a_warning() {
+        let dir = create_temp_dir("scaffer_test_create_parent_build").unwrap();
+        std::fs::write(
+            dir.join("Cargo.toml"),
+            "[workspace]\nmembers = \"crates\"\n",
+        )
+        .unwrap();
+
+        let config = Config::new(Config::bundled_template_dir());
+        let template: Template = serde_json::from_str(
+            r#"{
+                "structure": {
+                    "directories": ["src"],
+                    "files": [{ "path": "src/main.rs", "content": ["fn main() {}"] }]
+                },
+                "start_command": "cargo run"
+            }"#,
+        )
+        .unwrap();
+
+        let create_command = CreateCommand {
+            language: "Rust".to_string(),
+            name: "demo".to_string(),
+            path: dir.clone(),
+            prompter: Prompter::new(true, true),
+            output: Output::new(OutputFormat::Json),
+            ..Default::default()
+        };
+        let project = create_command.generate(&template, &config).unwrap();
+
+        assert_eq!(project.parent_build, None);
+        assert!(dir.join("demo/src/main.rs").exists());
+        assert!(
+            create_command.output.warnings()[0].contains("The workspace members are not a list")
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_create_members() {
+        let dir = create_temp_dir("scaffer_test_create_members").unwrap();
+        std::fs::create_dir_all(dir.join("workspace")).unwrap();
+
+        let config = Config::new(Config::bundled_template_dir());
+        let create_command = CreateCommand {
+            language: "Workspace".to_string(),
+            name: "workspace".to_string(),
+            path: dir.clone(),
+            prompter: Prompter::new(true, false),
+            run_hooks: false,
+            ..Default::default()
+        };
+        let members = vec![
+            "rust:cli".parse().unwrap(),
+            Member {
+                path: Some(PathBuf::from("services")),
+                ..Member::from_str("go:backend").unwrap()
+            },
+        ];
+
+        let prepared = create_command.prepare_members(&members, &config).unwrap();
+        let created = create_command.create_members(prepared, &config).unwrap();
+
+        assert_eq!(created.len(), 2);
+        assert_eq!(created[0].language, "Rust");
+        assert!(dir.join("workspace/cli/Cargo.toml").exists());
+        assert!(dir.join("workspace/services/backend/go.mod").exists());
+        assert_eq!(
+            created[1].next_steps[0],
+            format!("cd {}", dir.join("workspace/services/backend").display())
+        );
+        assert!(create_command
+            .prepare_members(&["rust:1cli".parse().unwrap()], &config)
+            .is_err());
+
+        // An invalid member is rejected before the workspace is generated
+        let mut create_command = CreateCommand {
+            prompter: Prompter::new(true, false),
+            run_hooks: false,
+            members: vec!["rust:cli".parse().unwrap(), "cobol:legacy".parse().unwrap()],
+            ..Default::default()
+        };
+        assert!(create_command
+            .run_create(None, Some("broken".to_string()), Some(dir.clone()), &config)
+            .is_err());
+        assert!(!dir.join("broken").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_run_command() {
+        let mut template: Template = serde_json::from_str(
+            r#"{
+                "structure": { "directories": [], "files": [] },
+                "start_command": "cargo run --bin {{project_name}}",
+                "tasks": { "test": "cargo test -p {{project_name}}" }
+            }"#,
+        )
+        .unwrap();
+        let create_command = CreateCommand {
+            language: "Rust".to_string(),
+            name: "demo".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            create_command.run_command(&template, None).unwrap(),
+            ("start".to_string(), "cargo run --bin demo".to_string())
+        );
+        assert_eq!(
+            create_command.run_command(&template, Some("test")).unwrap(),
+            ("test".to_string(), "cargo test -p demo".to_string())
+        );
+        assert!(create_command.run_command(&template, Some("lint")).is_err());
+
+        template
+            .tasks
+            .insert("run".to_string(), "cargo run --release".to_string());
+        assert_eq!(
+            create_command.run_command(&template, None).unwrap(),
+            ("run".to_string(), "cargo run --release".to_string())
+        );
+
+        let html = Template::load_template(
+            "html".to_string(),
+            &Config::new(Config::bundled_template_dir()),
+        )
+        .unwrap();
+        assert_eq!(create_command.run_command(&html, None).unwrap().0, "run");
+    }
+
+    #[test]
+    fn test_should_write_with_conflict_policy() {
+        let dir = create_temp_dir("scaffer_test_should_write").unwrap();
+        let file_path = dir.join("main.rs");
+        std::fs::write(&file_path, "old").unwrap();
+
+        let mut create_command = CreateCommand::default();
+        assert!(create_command
+            .should_write(&dir.join("new.rs"), "new")
+            .unwrap());
+        assert!(!create_command.should_write(&file_path, "old").unwrap());
+
+        create_command.conflict_policy = ConflictPolicy::Skip;
+        assert!(!create_command.should_write(&file_path, "new").unwrap());
+
+        create_command.conflict_policy = ConflictPolicy::Overwrite;
+        assert!(create_command.should_write(&file_path, "new").unwrap());
 
-        let gitignore = create_command.fetch_gitignore("Rust".to_string()).unwrap();
-        assert!(gitignore.contains("target"));
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 }
@@ -0,0 +1,363 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use super::GenerateCommand;
+use crate::utils::{Config, GitignoreSource, Manifest, Output, Style, Template};
+
+/// How a file of the project differs from the rendered template
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffStatus {
+    /// The file matches the template
+    Unchanged,
+    /// The file differs from the template
+    Modified,
+    /// The file of the template does not exist in the project
+    Missing,
+}
+
+/// The difference between a file of the project and the rendered template
+#[derive(Debug, Clone, Serialize)]
+pub struct FileDiff {
+    pub path: PathBuf,
+    pub status: DiffStatus,
+    pub additions: usize,
+    pub deletions: usize,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub diff: String,
+}
+
+/// The number of files per status
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DiffSummary {
+    pub unchanged: usize,
+    pub modified: usize,
+    pub missing: usize,
+}
+
+/// The difference between a project and its template
+#[derive(Debug, Clone, Serialize)]
+pub struct DiffReport {
+    pub template: String,
+    pub files: Vec<FileDiff>,
+    pub summary: DiffSummary,
+}
+
+pub struct DiffCommand {
+    pub project_path: PathBuf,
+    pub language: Option<String>,
+    pub variables: Vec<String>,
+    pub stat: bool,
+    pub output: Output,
+}
+
+impl DiffCommand {
+    /// Show the difference between the project and its rendered template
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration object
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Box<dyn std::error::Error>>` - The result of the diff command
+    ///
+    /// # Errors
+    ///
+    /// * If the template of the project cannot be determined or loaded
+    /// * If a variable is not in the form `NAME=VALUE`
+    /// * If the template cannot be rendered
+    pub fn run_diff(&self, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+        let manifest = self.manifest()?;
+        let template = Template::load_template(manifest.template.clone(), config)?;
+        let report =
+            self.diff_project(&manifest, &template, &GitignoreSource::from_config(config)?)?;
+
+        for file in &report.files {
+            if self.stat {
+                let status = match file.status {
+                    DiffStatus::Unchanged => continue,
+                    DiffStatus::Modified => self.output.paint("modified", Style::Warning),
+                    DiffStatus::Missing => self.output.paint("missing", Style::Error),
+                };
+                self.output.print(format!(
+                    " {} | {} {} ({})",
+                    file.path.display(),
+                    self.output
+                        .paint(&format!("+{}", file.additions), Style::Success),
+                    self.output
+                        .paint(&format!("-{}", file.deletions), Style::Error),
+                    status
+                ));
+                continue;
+            }
+
+            for line in file.diff.lines() {
+                if line.starts_with("+++") || line.starts_with("---") {
+                    self.output.print(self.output.paint(line, Style::Heading));
+                } else if line.starts_with('+') {
+                    self.output.print(self.output.paint(line, Style::Success));
+                } else if line.starts_with('-') {
+                    self.output.print(self.output.paint(line, Style::Error));
+                } else if line.starts_with("@@") {
+                    self.output.print(self.output.paint(line, Style::Highlight));
+                } else {
+                    self.output.print(line);
+                }
+            }
+        }
+
+        self.output.print(format!(
+            "\n{} {} unchanged, {} modified, {} missing",
+            self.output
+                .paint(&format!("{} template:", report.template), Style::Heading),
+            report.summary.unchanged,
+            report.summary.modified,
+            report.summary.missing
+        ));
+
+        self.output.result("diff", &report)
+    }
+
+    /// Compare the files of the project with the rendered template
+    ///
+    /// The diffs go from the rendered template to the working tree, so added lines are
+    /// local changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The manifest with the answers to render the template with
+    /// * `template` - The template of the project
+    /// * `gitignores` - The source of the gitignore templates
+    ///
+    /// # Returns
+    ///
+    /// * `Result<DiffReport, Box<dyn std::error::Error>>` - The difference per file
+    ///
+    /// # Errors
+    ///
+    /// * If a variable is not in the form `NAME=VALUE`
+    /// * If the template cannot be rendered
+    fn diff_project(
+        &self,
+        manifest: &Manifest,
+        template: &Template,
+        gitignores: &GitignoreSource,
+    ) -> Result<DiffReport, Box<dyn std::error::Error>> {
+        let mut manifest = manifest.clone();
+        for variable in &self.variables {
+            let (name, value) = variable
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid variable `{}`, use NAME=VALUE", variable))?;
+            manifest
+                .answers
+                .insert(name.trim().to_string(), value.to_string());
+        }
+
+        let project_path = self.project_path.canonicalize()?;
+        let rendered = manifest.render_project(template, &project_path, gitignores)?;
+
+        let mut files = vec![];
+        let mut summary = DiffSummary::default();
+        for (path, expected) in rendered {
+            let current = std::fs::read_to_string(self.project_path.join(&path)).ok();
+            let status = match &current {
+                None => DiffStatus::Missing,
+                Some(current) if current == &expected => DiffStatus::Unchanged,
+                Some(_) => DiffStatus::Modified,
+            };
+            match status {
+                DiffStatus::Unchanged => summary.unchanged += 1,
+                DiffStatus::Modified => summary.modified += 1,
+                DiffStatus::Missing => summary.missing += 1,
+            }
+
+            let (mut additions, mut deletions, mut diff) = (0, 0, String::new());
+            if status != DiffStatus::Unchanged {
+                let current = current.unwrap_or_default();
+                let patch = diffy::DiffOptions::new()
+                    .set_original_filename(format!("a/{}", path.display()))
+                    .set_modified_filename(if status == DiffStatus::Missing {
+                        "/dev/null".to_string()
+                    } else {
+                        format!("b/{}", path.display())
+                    })
+                    .create_patch(&expected, &current);
+                for hunk in patch.hunks() {
+                    for line in hunk.lines() {
+                        match line {
+                            diffy::Line::Insert(_) => additions += 1,
+                            diffy::Line::Delete(_) => deletions += 1,
+                            diffy::Line::Context(_) => {}
+                        }
+                    }
+                }
+                diff = patch.to_string();
+            }
+
+            files.push(FileDiff {
+                path,
+                status,
+                additions,
+                deletions,
+                diff,
+            });
+        }
+
+        Ok(DiffReport {
+            template: manifest.template,
+            files,
+            summary,
+        })
+    }
+
+    /// The manifest of the project
+    ///
+    /// Projects without a manifest get one with the language from `--language` or detected
+    /// from the marker files and the directory name as project name.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Manifest, Box<dyn std::error::Error>>` - The manifest of the project
+    ///
+    /// # Errors
+    ///
+    /// * If the project has no manifest and its language cannot be detected
+    fn manifest(&self) -> Result<Manifest, Box<dyn std::error::Error>> {
+        if let Ok(manifest) = Manifest::load(&self.project_path) {
+            return Ok(manifest);
+        }
+
+        let language = match &self.language {
+            Some(language) => language.clone(),
+            None => GenerateCommand::detect_language(&self.project_path).ok_or(
+                "The project has no manifest and its language could not be detected, use --language to set it",
+            )?,
+        };
+
+        Ok(Manifest {
+            scaffer_version: env!("CARGO_PKG_VERSION").to_string(),
+            template: language.to_lowercase(),
+            template_version: None,
+            template_hash: String::new(),
+            answers: BTreeMap::from([
+                (
+                    "project_name".to_string(),
+                    Self::directory_name(&self.project_path)?,
+                ),
+                ("language".to_string(), language),
+            ]),
+            files: BTreeMap::new(),
+            template_snapshot: None,
+            conflicts: BTreeSet::new(),
+        })
+    }
+
+    /// The name of the project directory
+    ///
+    /// # Arguments
+    ///
+    /// * `project_path` - The root directory of the project
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, Box<dyn std::error::Error>>` - The name of the directory
+    fn directory_name(project_path: &Path) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(project_path
+            .canonicalize()?
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default())
+    }
+}
+
+impl Default for DiffCommand {
+    fn default() -> Self {
+        Self {
+            project_path: std::env::current_dir().expect("Failed to use current directory"),
+            language: None,
+            variables: vec![],
+            stat: false,
+            output: Output::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_temp_dir;
+
+    #[test]
+    fn test_diff_project() {
+        let dir = create_temp_dir("scaffer_test_diff_project").unwrap();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        let gitignores = GitignoreSource {
+            local_dir: None,
+            cache_dir: dir.join("cache"),
+            base_url: String::new(),
+        };
+
+        let template: Template = serde_json::from_str(
+            r##"{
+                "structure": {
+                    "directories": ["src"],
+                    "files": [
+                        { "path": "src/main.rs", "content": ["fn main() {", "    println!(\"{{project_name}}\");", "}"] },
+                        { "path": "Cargo.toml", "content": ["[package]", "name = \"{{project_name}}\""] },
+                        { "path": "README.md", "content": ["# {{project_name}}"] }
+                    ]
+                },
+                "start_command": "cargo run"
+            }"##,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("src/main.rs"),
+            "fn main() {\n    println!(\"renamed\");\n}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+
+        let answers = BTreeMap::from([("project_name".to_string(), "demo".to_string())]);
+        let manifest = Manifest::new("rust", &template, answers, &dir).unwrap();
+        let diff_command = DiffCommand {
+            project_path: dir.clone(),
+            ..Default::default()
+        };
+
+        let report = diff_command
+            .diff_project(&manifest, &template, &gitignores)
+            .unwrap();
+        assert_eq!(
+            report.summary,
+            DiffSummary {
+                unchanged: 1,
+                modified: 1,
+                missing: 1
+            }
+        );
+        let main = &report.files[2];
+        assert_eq!(main.path, PathBuf::from("src/main.rs"));
+        assert_eq!((main.additions, main.deletions), (1, 1));
+        assert!(main.diff.contains("--- a/src/main.rs\n+++ b/src/main.rs"));
+        assert!(main.diff.contains("+    println!(\"renamed\");"));
+
+        let diff_command = DiffCommand {
+            project_path: dir.clone(),
+            variables: vec!["project_name=renamed".to_string()],
+            ..Default::default()
+        };
+        let report = diff_command
+            .diff_project(&manifest, &template, &gitignores)
+            .unwrap();
+        assert_eq!(report.files[2].status, DiffStatus::Unchanged);
+        assert_eq!(report.files[0].status, DiffStatus::Modified);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,206 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use inquire::{Select, Text};
+
+use crate::utils::{
+    validate_identifier, Config, GeneratedFile, Manifest, Output, Prompter, Template,
+};
+
+/// Marker files to detect the language of an existing project, most specific first
+const LANGUAGE_MARKERS: &[(&str, &str)] = &[
+    ("Cargo.toml", "Rust"),
+    ("go.mod", "Go"),
+    ("pom.xml", "Java"),
+    ("composer.json", "PHP"),
+    ("pyproject.toml", "Python"),
+    ("requirements.txt", "Python"),
+    ("main.py", "Python"),
+    ("src/main.cpp", "C++"),
+    ("src/main.c", "C"),
+    ("index.html", "HTML"),
+];
+
+pub struct GenerateCommand {
+    pub project_path: PathBuf,
+    pub language: Option<String>,
+    pub force: bool,
+    pub prompter: Prompter,
+    pub output: Output,
+}
+
+impl GenerateCommand {
+    /// Run a generator of the project template to add a component to the project
+    ///
+    /// # Arguments
+    ///
+    /// * `generator` - The name of the generator (e.g. module, package, page)
+    /// * `name` - The name of the component
+    /// * `config` - The configuration object
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Box<dyn std::error::Error>>` - The result of the generate command
+    ///
+    /// # Errors
+    ///
+    /// * If the language of the project cannot be detected
+    /// * If the template has no generator with the given name
+    /// * If the user input is invalid
+    /// * If the files cannot be generated
+    pub fn run_generate(
+        &self,
+        generator: Option<String>,
+        name: Option<String>,
+        config: &Config,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let language = match &self.language {
+            Some(language) => language.clone(),
+            None => Self::detect_language(&self.project_path)
+                .ok_or("Could not detect the language of the project, use --language to set it")?,
+        };
+
+        let template = Template::load_template(language.clone(), config)?;
+        if template.generators.is_empty() {
+            return Err(format!("The {} template has no generators", language).into());
+        }
+
+        let generator_names: Vec<String> = template
+            .generators
+            .iter()
+            .map(|generator| generator.name.clone())
+            .collect();
+        let generator_name = self.prompter.require(generator, "<GENERATOR>", || {
+            Select::new("Select a generator", generator_names.clone()).prompt()
+        })?;
+        let generator = template
+            .generators
+            .iter()
+            .find(|generator| generator.name.eq_ignore_ascii_case(&generator_name))
+            .ok_or_else(|| {
+                format!(
+                    "Unknown generator {}, available generators: {}",
+                    generator_name,
+                    generator_names.join(", ")
+                )
+            })?;
+
+        let name = self.prompter.require(name, "<NAME>", || {
+            Text::new("Enter the name of the component").prompt()
+        })?;
+        validate_identifier(&language, &name)
+            .map_err(|reason| format!("Invalid name `{}`: {}", name, reason))?;
+
+        let project_name = self
+            .project_path
+            .canonicalize()?
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let variables = HashMap::from([
+            ("name".to_string(), name.clone()),
+            ("project_name".to_string(), project_name),
+            ("language".to_string(), language.clone()),
+        ]);
+
+        self.output
+            .step(&format!("Running generator {}...", generator.name));
+        let (mut created, mut updated, mut unchanged) = (vec![], vec![], vec![]);
+        for file in template.run_generator(generator, &self.project_path, &variables, self.force)? {
+            match file {
+                GeneratedFile::Created(path) => {
+                    self.output.success(&format!("Created {}", path.display()));
+                    created.push(path);
+                }
+                GeneratedFile::Appended(path) => {
+                    self.output.success(&format!("Updated {}", path.display()));
+                    updated.push(path);
+                }
+                GeneratedFile::Unchanged(path) => {
+                    self.output
+                        .unchanged(&format!("Unchanged {}", path.display()));
+                    unchanged.push(path);
+                }
+            }
+        }
+
+        self.output.done(&format!(
+            "{} {} successfully generated!",
+            generator.name, name
+        ));
+
+        self.output.result(
+            "generate",
+            serde_json::json!({
+                "generator": generator.name,
+                "name": name,
+                "language": language,
+                "created": created,
+                "updated": updated,
+                "unchanged": unchanged,
+            }),
+        )
+    }
+
+    /// Detect the language of an existing project from its manifest or its marker files
+    ///
+    /// # Arguments
+    ///
+    /// * `project_path` - The root directory of the project
+    ///
+    /// # Returns
+    ///
+    /// * `Option<String>` - The detected language
+    pub(crate) fn detect_language(project_path: &Path) -> Option<String> {
+        if let Ok(manifest) = Manifest::load(project_path) {
+            return Some(manifest.template);
+        }
+
+        LANGUAGE_MARKERS
+            .iter()
+            .find(|(marker, _)| project_path.join(marker).exists())
+            .map(|(_, language)| language.to_string())
+    }
+}
+
+impl Default for GenerateCommand {
+    fn default() -> Self {
+        Self {
+            project_path: std::env::current_dir().expect("Failed to use current directory"),
+            language: None,
+            force: false,
+            prompter: Prompter::default(),
+            output: Output::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_temp_dir;
+
+    #[test]
+    fn test_detect_language() {
+        let dir = create_temp_dir("scaffer_test_detect_language").unwrap();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+
+        assert_eq!(GenerateCommand::detect_language(&dir), None);
+
+        std::fs::write(dir.join("src/main.cpp"), "").unwrap();
+        assert_eq!(
+            GenerateCommand::detect_language(&dir),
+            Some("C++".to_string())
+        );
+
+        std::fs::write(dir.join("Cargo.toml"), "").unwrap();
+        assert_eq!(
+            GenerateCommand::detect_language(&dir),
+            Some("Rust".to_string())
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,77 @@
+use crate::utils::{Config, GitignoreSource, Output};
+
+pub struct Gitignores;
+
+impl Gitignores {
+    /// List the available gitignore templates
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration object
+    ///
+    /// # Returns
+    ///
+    /// The names of the local, cached and bundled gitignore templates
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the cache directory cannot be found
+    pub fn list_gitignores(config: &Config) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        Ok(GitignoreSource::from_config(config)?.available())
+    }
+
+    /// Update the cached gitignore templates from the configured base URL
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - The gitignore templates to update, all available ones if empty
+    /// * `config` - The configuration object
+    /// * `output` - The output to report the progress to
+    ///
+    /// # Returns
+    ///
+    /// The result of updating the gitignore templates
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if one of the gitignore templates cannot be fetched
+    pub fn update_gitignores(
+        names: Vec<String>,
+        config: &Config,
+        output: &Output,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let source = GitignoreSource::from_config(config)?;
+
+        let names = if names.is_empty() {
+            source.available()
+        } else {
+            names
+        };
+
+        let mut updated = vec![];
+        let mut failed = vec![];
+        for name in names {
+            match source.fetch(&name, output) {
+                Ok(_) => {
+                    output.success(&name);
+                    updated.push(name);
+                }
+                Err(err) => {
+                    output.failure(&format!("{} ({})", name, err));
+                    failed.push(name);
+                }
+            }
+        }
+
+        if !failed.is_empty() {
+            return Err(format!("Could not update {}", failed.join(", ")).into());
+        }
+
+        output.line("Gitignore templates successfully updated!");
+
+        output.result(
+            "gitignore update",
+            serde_json::json!({ "updated": updated }),
+        )
+    }
+}
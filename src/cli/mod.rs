@@ -1,11 +1,23 @@
 use clap::Parser;
-pub use commands::{Commands, TemplatesCommand};
-pub use create::CreateCommand;
+
+use crate::utils::{OutputFormat, Verbosity};
+pub use commands::{Commands, GitignoreCommand, TemplatesCommand};
+pub use create::{ConflictPolicy, CreateCommand};
+pub use diff::DiffCommand;
+pub use generate::GenerateCommand;
+pub use gitignore::Gitignores;
+pub use run::RunCommand;
 pub use templates::Templates;
+pub use update::UpdateCommand;
 
 pub mod commands;
 pub mod create;
+pub mod diff;
+pub mod generate;
+pub mod gitignore;
+pub mod run;
 pub mod templates;
+pub mod update;
 
 #[derive(Parser)]
 #[clap(
@@ -16,4 +28,34 @@ pub mod templates;
 pub struct Cli {
     #[command(subcommand)]
     pub commands: Commands,
+    /// Answer all confirmations with yes and never prompt
+    #[arg(short, long, global = true)]
+    pub yes: bool,
+    /// Never prompt, take the default answers and fail if a required value is missing
+    #[arg(long, global = true)]
+    pub no_input: bool,
+    /// The format of the output, `json` prints a single structured result
+    #[arg(long, global = true, value_enum, default_value_t)]
+    pub output: OutputFormat,
+    /// Only print the results, warnings and errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+    /// Also print every file written and every network request
+    #[arg(short, long, global = true)]
+    pub verbose: bool,
+}
+
+impl Cli {
+    /// The verbosity selected with `--quiet` or `--verbose`
+    ///
+    /// # Returns
+    ///
+    /// * `Verbosity` - The verbosity of the output
+    pub fn verbosity(&self) -> Verbosity {
+        match (self.quiet, self.verbose) {
+            (true, _) => Verbosity::Quiet,
+            (_, true) => Verbosity::Verbose,
+            _ => Verbosity::Normal,
+        }
+    }
 }
@@ -0,0 +1,108 @@
+use std::path::PathBuf;
+
+use crate::utils::{Output, TaskFile, TaskRun, TASK_FILE};
+
+pub struct RunCommand {
+    pub project_path: PathBuf,
+    pub output: Output,
+}
+
+impl RunCommand {
+    /// Run a task of the project or list the available tasks
+    ///
+    /// The tasks are read from the task file in the project directory or one of its parents and
+    /// run by the system shell in the directory of the task file. The output of the task is
+    /// streamed to the terminal, with `--output json` it is captured into the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The name of the task, the tasks are listed if omitted
+    /// * `args` - Additional arguments appended to the command of the task
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Box<dyn std::error::Error>>` - The result of the run command
+    ///
+    /// # Errors
+    ///
+    /// * If no task file is found or the task is unknown
+    /// * If the command of the task cannot be started
+    /// * If the task exits with a non-zero status, as `TaskFailed` with its exit code
+    pub fn run_task(
+        &self,
+        task: Option<String>,
+        args: Vec<String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (project_path, tasks) = TaskFile::find(&self.project_path)?;
+
+        let Some(task) = task else {
+            self.output.print(format!(
+                "Available tasks in {}:",
+                project_path.join(TASK_FILE).display()
+            ));
+            for (name, command) in &tasks.tasks {
+                self.output.print(format!("- {}: {}", name, command));
+            }
+            return self
+                .output
+                .result("run", serde_json::json!({ "tasks": tasks.tasks }));
+        };
+
+        let command = tasks.command(&task, &args)?;
+        self.output.step(&format!("Running {}: {}", task, command));
+
+        let run = TaskRun::execute(&task, &command, &project_path, self.output.is_json())?;
+        self.output.result("run", &run)?;
+        run.check()?;
+
+        self.output.done(&format!("Task {} finished", task));
+        Ok(())
+    }
+}
+
+impl Default for RunCommand {
+    fn default() -> Self {
+        Self {
+            project_path: std::env::current_dir().expect("Failed to use current directory"),
+            output: Output::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    use crate::utils::{create_temp_dir, SilentReporter, TaskFailed};
+
+    #[test]
+    fn test_run_task() {
+        let dir = create_temp_dir("scaffer_test_run_task").unwrap();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(
+            dir.join(TASK_FILE),
+            "[tasks]\nbuild = \"echo built > out.txt\"\nfail = \"exit 3\"\n",
+        )
+        .unwrap();
+
+        let command = RunCommand {
+            project_path: dir.join("src"),
+            output: Output::default().with_reporter(Rc::new(SilentReporter)),
+        };
+
+        command.run_task(Some("build".to_string()), vec![]).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.join("out.txt")).unwrap().trim(),
+            "built"
+        );
+
+        let error = command
+            .run_task(Some("fail".to_string()), vec![])
+            .unwrap_err();
+        let failed = error.downcast_ref::<TaskFailed>().unwrap();
+        assert_eq!(failed.code, 3);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
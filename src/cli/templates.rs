@@ -1,11 +1,17 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
 
 use inquire::{
-    ui::{Color, RenderConfig, Styled},
-    Confirm, Editor, Select, Text,
+    ui::{RenderConfig, Styled},
+    Editor, Select, Text,
 };
 
-use crate::utils::{Config, File, Structure, Template};
+use crate::utils::{
+    Config, File, Formatting, Hooks, Output, Prompter, Structure, Style, Template, TemplateSource,
+    Theme,
+};
 
 pub struct Templates {
     pub templates: Vec<Template>,
@@ -62,6 +68,10 @@ impl Templates {
     /// # Arguments
     ///
     /// * `language` - The language of the template
+    /// * `start_command` - The start command of the template
+    /// * `directories` - The directories of the template, comma separated
+    /// * `prompter` - The prompter used to ask for missing values
+    /// * `output` - The output to report the result to
     ///
     /// # Returns
     ///
@@ -70,16 +80,20 @@ impl Templates {
     /// # Errors
     ///
     /// This function will return an error if the template cannot be added
-    pub fn add_template(language: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
-        let language = if let Some(language) = language {
-            language
-        } else {
-            Text::new("Enter the language of the template. (e.g. python, rust etc)").prompt()?
-        };
+    pub fn add_template(
+        language: Option<String>,
+        start_command: Option<String>,
+        directories: Option<String>,
+        prompter: &Prompter,
+        output: &Output,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let language = prompter.require(language, "--language", || {
+            Text::new("Enter the language of the template. (e.g. python, rust etc)").prompt()
+        })?;
 
-        let directories = Self::parse_directories()?;
+        let directories = Self::parse_directories(directories, prompter)?;
 
-        let files = Self::parse_files()?;
+        let files = Self::parse_files(prompter, output.theme())?;
 
         let structure = Structure {
             directories,
@@ -88,19 +102,31 @@ impl Templates {
                 .map(|(path, content)| File {
                     path: PathBuf::from(path),
                     content: vec![content.clone()],
+                    formatting: Formatting::default(),
                 })
                 .collect(),
         };
 
-        let start_command = Text::new("Please enter the start command:")
-            .with_help_message(
-                "Enter the start command for the project. (e.g. cargo run, python main.py)",
-            )
-            .prompt()?;
+        let start_command = prompter.require(start_command, "--start-command", || {
+            Text::new("Please enter the start command:")
+                .with_help_message(
+                    "Enter the start command for the project. (e.g. cargo run, python main.py)",
+                )
+                .prompt()
+        })?;
 
         let template = Template {
+            version: None,
             structure,
+            tasks: BTreeMap::from([("run".to_string(), start_command.clone())]),
             start_command,
+            hooks: Hooks::default(),
+            gitignore: vec![language.clone()],
+            readme: vec![],
+            generators: vec![],
+            members: vec![],
+            formatting: Formatting::default(),
+            source: None,
         };
 
         let config = Config::load()?;
@@ -109,11 +135,15 @@ impl Templates {
             .join(format!("{}.json", language.to_lowercase()));
 
         let template = serde_json::to_string(&template)?;
-        std::fs::write(template_path, template)?;
+        std::fs::write(&template_path, template)?;
+        output.detail(&format!("Wrote {}", template_path.display()));
 
-        println!("Successfully added new template");
+        output.line("Successfully added new template");
 
-        Ok(())
+        output.result(
+            "templates add",
+            serde_json::json!({ "template": language.to_lowercase(), "path": template_path }),
+        )
     }
 
     /// Remove a template
@@ -121,6 +151,8 @@ impl Templates {
     /// # Arguments
     ///
     /// * `template` - The template to remove
+    /// * `prompter` - The prompter used to ask for missing values
+    /// * `output` - The output to report the result to
     ///
     /// # Returns
     ///
@@ -129,38 +161,45 @@ impl Templates {
     /// # Errors
     ///
     /// This function will return an error if the template cannot be removed
-    pub fn remove_template(template: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn remove_template(
+        template: Option<String>,
+        prompter: &Prompter,
+        output: &Output,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         let config = Config::load()?;
 
         let template_list = Self::list_templates(template.clone(), &config)?;
 
-        let template_path = if let Some(template) = template {
-            config
-                .template_dir
-                .join(format!("{}.json", template.to_lowercase()))
-        } else {
-            let selelected_template =
-                Select::new("Please select the template to remove:", template_list).prompt()?;
-            config
-                .template_dir
-                .join(format!("{}.json", selelected_template.to_lowercase()))
-        };
+        let template = prompter.require(template, "--template", || {
+            Select::new("Please select the template to remove:", template_list).prompt()
+        })?;
+        let template_path = config
+            .template_dir
+            .join(format!("{}.json", template.to_lowercase()));
 
-        if template_path.exists() {
+        let removed = template_path.exists();
+        if removed {
             std::fs::remove_file(template_path)?;
-            println!("Successfully removed template");
+            output.line("Successfully removed template");
         } else {
-            println!("Template does not exist");
+            output.warning("Template does not exist");
         }
 
-        Ok(())
+        output.result(
+            "templates remove",
+            serde_json::json!({ "template": template.to_lowercase(), "removed": removed }),
+        )
     }
 
     /// Update a template
     ///
+    /// A `run` task that repeats the start command is updated together with it.
+    ///
     /// # Arguments
     ///
     /// * `language` - The language of the template
+    /// * `prompter` - The prompter used to ask for missing values
+    /// * `output` - The output to report the result to
     ///
     /// # Returns
     ///
@@ -168,52 +207,63 @@ impl Templates {
     ///
     /// # Errors
     ///
-    /// This function will return an error if the template cannot be updated
-    pub fn update_template(language: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    /// This function will return an error if the template cannot be updated or if it runs without interactive input
+    pub fn update_template(
+        language: Option<String>,
+        prompter: &Prompter,
+        output: &Output,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if !prompter.interactive {
+            return Err("Updating a template requires interactive input".into());
+        }
+
         let config = Config::load()?;
         let template_list = Self::list_templates(language.clone(), &config)?;
 
-        let template_path = if let Some(language) = language {
-            config
-                .template_dir
-                .join(format!("{}.json", language.to_lowercase()))
-        } else {
-            let selected_template =
-                Select::new("Please select the template to update:", template_list).prompt()?;
-            config
-                .template_dir
-                .join(format!("{}.json", selected_template.to_lowercase()))
-        };
+        let language = prompter.require(language, "--language", || {
+            Select::new("Please select the template to update:", template_list).prompt()
+        })?;
+        let template_path = config
+            .template_dir
+            .join(format!("{}.json", language.to_lowercase()));
 
-        if template_path.exists() {
+        let updated = template_path.exists();
+        if updated {
             let template = std::fs::read_to_string(template_path.clone())?;
             let mut template: Template = serde_json::from_str(&template)?;
 
-            println!("Current start command: {}", template.start_command);
-            if Confirm::new("Do you want to update the start command?").prompt()? {
-                let start_command = Text::new("Please enter the start command:")
-                    .with_help_message(
-                        "Enter the start command for the project. (e.g. cargo run, python main.py)",
-                    )
-                    .prompt()?;
+            output.print(format!("Current start command: {}", template.start_command));
+            if prompter.confirm("Do you want to update the start command?", false)? {
+                let start_command = prompter.require(None, "start command", || {
+                    Text::new("Please enter the start command:")
+                        .with_help_message(
+                            "Enter the start command for the project. (e.g. cargo run, python main.py)",
+                        )
+                        .prompt()
+                })?;
+                if let Some(run) = template.tasks.get_mut("run") {
+                    if *run == template.start_command {
+                        *run = start_command.clone();
+                    }
+                }
                 template.start_command = start_command;
             }
 
-            println!("Current directories:");
+            output.print("Current directories:");
             for dir in &template.structure.directories {
-                println!("  - {}", dir);
+                output.print(format!("  - {}", dir));
             }
-            if Confirm::new("Do you want to update the directories?").prompt()? {
-                let directories = Self::parse_directories()?;
+            if prompter.confirm("Do you want to update the directories?", false)? {
+                let directories = Self::parse_directories(None, prompter)?;
                 template.structure.directories = directories;
             }
 
-            println!("Current files:");
+            output.print("Current files:");
             for file in &template.structure.files {
-                println!("  - {}", file.path.display());
+                output.print(format!("  - {}", file.path.display()));
             }
-            if Confirm::new("Do you want to update the files?").prompt()? {
-                let files = Self::parse_files()?;
+            if prompter.confirm("Do you want to update the files?", false)? {
+                let files = Self::parse_files(prompter, output.theme())?;
                 let mut new_files = Vec::new();
 
                 for old_file in template.structure.files {
@@ -223,15 +273,17 @@ impl Templates {
                         new_files.push(File {
                             path: old_file.path,
                             content: content_history,
+                            formatting: old_file.formatting,
                         });
                     }
                 }
 
                 for (path, content) in files {
-                    if !new_files.iter().any(|f| f.path == PathBuf::from(&path)) {
+                    if !new_files.iter().any(|f| f.path == Path::new(&path)) {
                         new_files.push(File {
                             path: PathBuf::from(path),
                             content: vec![content],
+                            formatting: Formatting::default(),
                         });
                     }
                 }
@@ -240,19 +292,158 @@ impl Templates {
             }
 
             let template = serde_json::to_string(&template)?;
-            std::fs::write(template_path, template)?;
-            println!("Template successfully updated!");
+            std::fs::write(&template_path, template)?;
+            output.detail(&format!("Wrote {}", template_path.display()));
+            output.line("Template successfully updated!");
+        } else {
+            output.warning("Template does not exist");
+        }
+
+        output.result(
+            "templates update",
+            serde_json::json!({ "template": language.to_lowercase(), "updated": updated }),
+        )
+    }
+
+    /// Install the templates of a git repository into the template directory
+    ///
+    /// The source is written as `<repository>[#ref][:subdir]`. The subdirectory can be a single
+    /// template file or a directory of template files. Every installed template records its
+    /// source, so installing from the same repository and subdirectory again upgrades it without
+    /// asking. The hooks of a template run on every `create`, so they are listed and have to be
+    /// confirmed unless an upgrade keeps them unchanged. Without interactive input hooks are only
+    /// trusted with `trust_hooks`, `--yes` does not confirm them.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The repository to install the templates from
+    /// * `trust_hooks` - Install templates with hooks without asking
+    /// * `prompter` - The prompter used to confirm hooks and overwriting other templates
+    /// * `output` - The output to report the result to
+    /// * `config` - The configuration object
+    ///
+    /// # Returns
+    ///
+    /// The result of installing the templates
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the repository cannot be cloned, contains no templates
+    /// or a template cannot be written
+    pub fn install_template(
+        source: String,
+        trust_hooks: bool,
+        prompter: &Prompter,
+        output: &Output,
+        config: &Config,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let source = source.parse::<TemplateSource>()?.absolute();
+
+        output.step(&format!("Cloning {}...", source));
+        let checkout = source.checkout()?;
+        let templates = source.templates(&checkout, output)?;
+
+        let mut installed = vec![];
+        for (name, template) in templates {
+            let template_path = config.template_dir.join(format!("{}.json", name));
+
+            let existing = std::fs::read_to_string(&template_path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<Template>(&content).ok());
+            let upgrade = existing.as_ref().is_some_and(|existing| {
+                existing.source.as_ref().is_some_and(|existing| {
+                    existing.repository == source.repository && existing.subdir == source.subdir
+                })
+            });
+            if template_path.exists()
+                && !upgrade
+                && !prompter.confirm(
+                    &format!("The template {} already exists, overwrite it? [y/n]", name),
+                    false,
+                )?
+            {
+                output.skipped(&format!("Template {} not installed", name));
+                continue;
+            }
+
+            let hooks = template
+                .hooks
+                .pre_generate
+                .iter()
+                .chain(&template.hooks.post_generate)
+                .map(|hook| hook.command.as_str())
+                .collect::<Vec<_>>();
+            let trusted = upgrade
+                && existing.as_ref().is_some_and(|existing| {
+                    serde_json::to_value(&existing.hooks).ok()
+                        == serde_json::to_value(&template.hooks).ok()
+                });
+            if !hooks.is_empty() && !trusted {
+                output.warning(&format!(
+                    "The template {} runs these commands when a project is created:",
+                    name
+                ));
+                for command in &hooks {
+                    output.item(command);
+                }
+                let accepted = trust_hooks
+                    || (prompter.interactive
+                        && prompter.confirm(
+                            &format!("Trust the hooks of the template {}? [y/n]", name),
+                            false,
+                        )?);
+                if !accepted {
+                    output.skipped(&format!(
+                        "Template {} not installed, use --trust-hooks to install it without interactive input",
+                        name
+                    ));
+                    continue;
+                }
+            }
+
+            std::fs::write(
+                &template_path,
+                serde_json::to_string_pretty(&template)? + "\n",
+            )?;
+            output.detail(&format!("Wrote {}", template_path.display()));
+            if upgrade {
+                output.success(&format!("Template {} upgraded", name));
+            } else {
+                output.success(&format!("Template {} installed", name));
+            }
+            installed.push(name);
+        }
+
+        if installed.is_empty() {
+            output.skipped(&format!("No templates installed from {}", source));
         } else {
-            println!("Template does not exist");
+            output.done(&format!(
+                "Installed {} from {} at {}",
+                installed.join(", "),
+                source,
+                &checkout.commit()[..checkout.commit().len().min(12)]
+            ));
         }
 
-        Ok(())
+        output.result(
+            "templates install",
+            serde_json::json!({
+                "source": source.to_string(),
+                "commit": checkout.commit(),
+                "templates": installed,
+            }),
+        )
     }
 
     /// Parse the directories
     ///
     /// Take the input from the user to add the directories information
     ///
+    /// # Arguments
+    ///
+    /// * `directories` - The directories given on the command line, comma separated
+    /// * `prompter` - The prompter used to ask for the directories
+    ///
     /// # Returns
     ///
     /// The list of directories
@@ -260,13 +451,17 @@ impl Templates {
     /// # Errors
     ///
     /// This function will return an error if the directories cannot be parsed
-    fn parse_directories() -> Result<Vec<String>, Box<dyn std::error::Error>> {
-        let mut directories = vec![];
-
-        let add_dictionary = Text::new("Please enter your dictonaries:")
-            .with_help_message("Enter your directonaries comma sperated. (e.g src, assets)")
-            .prompt()?;
+    fn parse_directories(
+        directories: Option<String>,
+        prompter: &Prompter,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let add_dictionary = prompter.require(directories, "--directories", || {
+            Text::new("Please enter your dictonaries:")
+                .with_help_message("Enter your directonaries comma sperated. (e.g src, assets)")
+                .prompt()
+        })?;
 
+        let mut directories = vec![];
         for directory in add_dictionary.split(",") {
             directories.push(directory.to_string());
         }
@@ -278,6 +473,11 @@ impl Templates {
     ///
     /// Take the input from the user to add the files information
     ///
+    /// # Arguments
+    ///
+    /// * `prompter` - The prompter used to ask for the files
+    /// * `theme` - The theme of the content prompt
+    ///
     /// # Returns
     ///
     /// The list of files
@@ -285,11 +485,14 @@ impl Templates {
     /// # Errors
     ///
     /// This function will return an error if the files cannot be parsed
-    fn parse_files() -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+    fn parse_files(
+        prompter: &Prompter,
+        theme: &Theme,
+    ) -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
         let mut files = HashMap::new();
 
         loop {
-            let add_file = Confirm::new("Do you want to add a file?").prompt()?;
+            let add_file = prompter.confirm("Do you want to add a file?", false)?;
 
             if !add_file {
                 break;
@@ -312,7 +515,7 @@ impl Templates {
                         substr
                     }
                 })
-                .with_render_config(Self::content_render_config())
+                .with_render_config(Self::content_render_config(theme))
                 .prompt()?;
 
             files.insert(path, content);
@@ -323,15 +526,16 @@ impl Templates {
 
     /// The render configuration for the content
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// The render configuration for the content
+    /// * `theme` - The theme of the prompts
     ///
-    /// # Errors
+    /// # Returns
     ///
-    /// This function will return an error if the render configuration cannot be created
-    fn content_render_config() -> RenderConfig<'static> {
-        RenderConfig::default()
-            .with_canceled_prompt_indicator(Styled::new("<skipped>").with_fg(Color::DarkYellow))
+    /// The render configuration of the theme with a skipped indicator
+    fn content_render_config(theme: &Theme) -> RenderConfig<'static> {
+        theme.render_config().with_canceled_prompt_indicator(
+            Styled::new("<skipped>").with_style_sheet(theme.prompt_style(Style::Warning)),
+        )
     }
 }
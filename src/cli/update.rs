@@ -0,0 +1,437 @@
+use std::{collections::BTreeSet, path::PathBuf};
+
+use serde::Serialize;
+
+use crate::utils::{Config, GitignoreSource, Manifest, Output, Template, MANIFEST_FILE};
+
+/// What an update did with a file of the project
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "path", rename_all = "lowercase")]
+pub enum UpdatedFile {
+    /// The file was added by the new template
+    Added(PathBuf),
+    /// The changes of the template were merged into the file
+    Merged(PathBuf),
+    /// The file was removed from the template and had no local changes
+    Removed(PathBuf),
+    /// The file was removed from the template but has local changes
+    Kept(PathBuf),
+    /// The changes of the template collide with local changes
+    Conflicted(PathBuf),
+    /// The file was added by the template but already exists locally and is left as it is
+    Existing(PathBuf),
+}
+
+pub struct UpdateCommand {
+    pub project_path: PathBuf,
+    pub output: Output,
+}
+
+impl UpdateCommand {
+    /// Update the project to the current version of its template
+    ///
+    /// The project is rendered from the template snapshot in its manifest and from the
+    /// current template with the recorded answers. The difference between both is merged
+    /// into the files of the project.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration object
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Box<dyn std::error::Error>>` - The result of the update command
+    ///
+    /// # Errors
+    ///
+    /// * If the project has no manifest or the manifest has no template snapshot
+    /// * If a previous update left conflict markers that are not resolved yet
+    /// * If the template cannot be loaded or rendered
+    /// * If the changes of the template conflict with local changes
+    pub fn run_update(&self, config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+        let mut manifest = Manifest::load(&self.project_path)?;
+        let template = Template::load_template(manifest.template.clone(), config)?;
+
+        let resolved = self.resolve_conflicts(&mut manifest)?;
+
+        let hash = Manifest::template_hash(&template)?;
+        if hash == manifest.template_hash {
+            if resolved {
+                manifest.save(&self.project_path)?;
+            }
+            self.output.done(&format!(
+                "The project is up to date with the {} template",
+                manifest.template
+            ));
+            return self.output.result(
+                "update",
+                serde_json::json!({ "template": manifest.template, "files": [] }),
+            );
+        }
+
+        self.output.step(&format!(
+            "Updating from the {} template...",
+            manifest.template
+        ));
+
+        let gitignores = GitignoreSource::from_config(config)?;
+        let updated = self.update_project(&mut manifest, &template, &gitignores)?;
+
+        let mut conflicts = vec![];
+        for file in &updated {
+            match file {
+                UpdatedFile::Added(path) => {
+                    self.output.success(&format!("Added {}", path.display()))
+                }
+                UpdatedFile::Merged(path) => {
+                    self.output.success(&format!("Updated {}", path.display()))
+                }
+                UpdatedFile::Removed(path) => {
+                    self.output.success(&format!("Removed {}", path.display()))
+                }
+                UpdatedFile::Kept(path) => self.output.warning(&format!(
+                    "Kept {} (removed from the template but modified locally)",
+                    path.display()
+                )),
+                UpdatedFile::Conflicted(path) => {
+                    self.output
+                        .failure(&format!("Conflict in {}", path.display()));
+                    conflicts.push(path.display().to_string());
+                }
+                UpdatedFile::Existing(path) => self.output.warning(&format!(
+                    "Kept {} (added by the template but already exists locally)",
+                    path.display()
+                )),
+            }
+        }
+
+        manifest.save(&self.project_path)?;
+        self.output.detail(&format!(
+            "Wrote {}",
+            self.project_path.join(MANIFEST_FILE).display()
+        ));
+
+        if !conflicts.is_empty() {
+            return Err(format!(
+                "The update left conflict markers in: {}. Resolve them and commit the result",
+                conflicts.join(", ")
+            )
+            .into());
+        }
+
+        self.output.done(&format!(
+            "Project updated to the current {} template!",
+            manifest.template
+        ));
+
+        self.output.result(
+            "update",
+            serde_json::json!({ "template": manifest.template, "files": updated }),
+        )
+    }
+
+    /// Check that the conflicts of a previous update have been resolved
+    ///
+    /// A conflicted file is resolved once it has no conflict markers left, its checksum is
+    /// then recorded in the manifest.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The manifest of the project
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, Box<dyn std::error::Error>>` - Whether conflicts have been resolved
+    ///
+    /// # Errors
+    ///
+    /// * If a conflicted file still has conflict markers
+    fn resolve_conflicts(
+        &self,
+        manifest: &mut Manifest,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let mut unresolved = vec![];
+        for path in &manifest.conflicts {
+            match std::fs::read_to_string(self.project_path.join(path)) {
+                Ok(content) if has_conflict_markers(&content) => {
+                    unresolved.push(path.display().to_string())
+                }
+                Ok(content) => {
+                    manifest
+                        .files
+                        .insert(path.clone(), Manifest::checksum(content.as_bytes()));
+                }
+                Err(_) => {
+                    manifest.files.remove(path);
+                }
+            }
+        }
+
+        if !unresolved.is_empty() {
+            return Err(format!(
+                "The last update left conflict markers in: {}. Resolve them before updating again",
+                unresolved.join(", ")
+            )
+            .into());
+        }
+
+        let resolved = !manifest.conflicts.is_empty();
+        manifest.conflicts.clear();
+        Ok(resolved)
+    }
+
+    /// Merge the changes between the template snapshot of the manifest and the new template
+    /// into the project
+    ///
+    /// Files that only changed in the template are replaced, files that changed on both sides
+    /// are merged line by line and get conflict markers where the changes collide. Files
+    /// deleted by the user stay deleted and files the template adds that already exist locally
+    /// are left as they are. The manifest records the checksums of the written files, conflicted
+    /// files keep their previous checksum until the conflicts are resolved.
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - The manifest of the project
+    /// * `template` - The new template
+    /// * `gitignores` - The source of the gitignore templates
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<UpdatedFile>, Box<dyn std::error::Error>>` - The files the update touched
+    ///
+    /// # Errors
+    ///
+    /// * If the manifest has no template snapshot
+    /// * If the templates cannot be rendered
+    /// * If a file cannot be read or written
+    fn update_project(
+        &self,
+        manifest: &mut Manifest,
+        template: &Template,
+        gitignores: &GitignoreSource,
+    ) -> Result<Vec<UpdatedFile>, Box<dyn std::error::Error>> {
+        let snapshot = manifest.template_snapshot.clone().ok_or(
+            "The manifest has no template snapshot, the project was created by an older scaffer version",
+        )?;
+
+        let project_path = self.project_path.canonicalize()?;
+        let base = manifest.render_project(&snapshot, &project_path, gitignores)?;
+        let new = manifest.render_project(template, &project_path, gitignores)?;
+
+        for directory in &template.structure.directories {
+            std::fs::create_dir_all(self.project_path.join(directory))?;
+        }
+
+        let mut updated = vec![];
+        let paths: BTreeSet<&PathBuf> = base.keys().chain(new.keys()).collect();
+        for path in paths {
+            let file_path = self.project_path.join(path);
+            let current = std::fs::read_to_string(&file_path).ok();
+
+            match (base.get(path), new.get(path), current) {
+                (Some(base), Some(new), _) if base == new => {}
+                (Some(base), None, Some(current)) => {
+                    if &current == base {
+                        std::fs::remove_file(&file_path)?;
+                        manifest.files.remove(path);
+                        updated.push(UpdatedFile::Removed(path.clone()));
+                    } else {
+                        updated.push(UpdatedFile::Kept(path.clone()));
+                    }
+                }
+                (Some(_), None, None) => {
+                    manifest.files.remove(path);
+                }
+                (Some(_), Some(_), None) => {}
+                (None, Some(new), None) => {
+                    if let Some(parent) = file_path.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    std::fs::write(&file_path, new)?;
+                    manifest
+                        .files
+                        .insert(path.clone(), Manifest::checksum(new.as_bytes()));
+                    updated.push(UpdatedFile::Added(path.clone()));
+                }
+                (_, Some(new), Some(current)) if &current == new => {
+                    manifest
+                        .files
+                        .insert(path.clone(), Manifest::checksum(new.as_bytes()));
+                }
+                (None, Some(_), Some(_)) => {
+                    updated.push(UpdatedFile::Existing(path.clone()));
+                }
+                (Some(base), Some(new), Some(current)) => match diffy::merge(base, &current, new) {
+                    Ok(merged) => {
+                        std::fs::write(&file_path, &merged)?;
+                        manifest
+                            .files
+                            .insert(path.clone(), Manifest::checksum(merged.as_bytes()));
+                        updated.push(UpdatedFile::Merged(path.clone()));
+                    }
+                    Err(conflicted) => {
+                        std::fs::write(&file_path, conflicted)?;
+                        manifest.conflicts.insert(path.clone());
+                        updated.push(UpdatedFile::Conflicted(path.clone()));
+                    }
+                },
+                (None, None, _) => {}
+            }
+        }
+
+        manifest.template_version = template.version.clone();
+        manifest.template_hash = Manifest::template_hash(template)?;
+        manifest.template_snapshot = Some(template.clone());
+
+        Ok(updated)
+    }
+}
+
+/// Whether a file still has the conflict markers of a merge
+///
+/// # Arguments
+///
+/// * `content` - The content of the file
+///
+/// # Returns
+///
+/// * `bool` - Whether a line starts with a conflict marker
+fn has_conflict_markers(content: &str) -> bool {
+    content
+        .lines()
+        .any(|line| line.starts_with("<<<<<<<") || line.starts_with(">>>>>>>"))
+}
+
+impl Default for UpdateCommand {
+    fn default() -> Self {
+        Self {
+            project_path: std::env::current_dir().expect("Failed to use current directory"),
+            output: Output::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_temp_dir;
+    use std::{collections::BTreeMap, path::Path};
+
+    fn template(files: &str) -> Template {
+        serde_json::from_str(&format!(
+            r#"{{
+                "structure": {{ "directories": ["src"], "files": {} }},
+                "start_command": "cargo run",
+                "readme": ["{{{{project_name}}}}"]
+            }}"#,
+            files
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_update_project() {
+        let dir = create_temp_dir("scaffer_test_update_project").unwrap();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        let gitignores = GitignoreSource {
+            local_dir: None,
+            cache_dir: dir.join("cache"),
+            base_url: String::new(),
+        };
+
+        let old = template(
+            r#"[
+                { "path": "src/main.rs", "content": ["fn main() {", "    println!(\"{{project_name}}\");", "}"] },
+                { "path": "src/lib.rs", "content": ["// lib", "pub mod a;", "// end"] },
+                { "path": "old.txt", "content": ["old"] }
+            ]"#,
+        );
+        let answers = BTreeMap::from([("project_name".to_string(), "demo".to_string())]);
+        let mut manifest = Manifest::new("rust", &old, answers, &dir).unwrap();
+        for (path, content) in manifest.render_project(&old, &dir, &gitignores).unwrap() {
+            std::fs::write(dir.join(&path), &content).unwrap();
+            manifest
+                .files
+                .insert(path, Manifest::checksum(content.as_bytes()));
+        }
+
+        // Local changes: an edit at the top of main.rs and a conflicting edit in lib.rs
+        std::fs::write(
+            dir.join("src/main.rs"),
+            "// my header\nfn main() {\n    println!(\"demo\");\n}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "// lib\npub mod mine;\n// end\n").unwrap();
+        std::fs::write(dir.join("notes.txt"), "my notes\n").unwrap();
+        let lib_checksum = manifest.files[Path::new("src/lib.rs")].clone();
+
+        let new = template(
+            r#"[
+                { "path": "src/main.rs", "content": ["fn main() {", "    println!(\"{{project_name}}\");", "    run();", "}"] },
+                { "path": "src/lib.rs", "content": ["// lib", "pub mod b;", "// end"] },
+                { "path": "new.txt", "content": ["new"] },
+                { "path": "notes.txt", "content": ["notes"] }
+            ]"#,
+        );
+        let update_command = UpdateCommand {
+            project_path: dir.clone(),
+            ..Default::default()
+        };
+        let updated = update_command
+            .update_project(&mut manifest, &new, &gitignores)
+            .unwrap();
+
+        assert_eq!(
+            updated,
+            vec![
+                UpdatedFile::Added(PathBuf::from("new.txt")),
+                UpdatedFile::Existing(PathBuf::from("notes.txt")),
+                UpdatedFile::Removed(PathBuf::from("old.txt")),
+                UpdatedFile::Conflicted(PathBuf::from("src/lib.rs")),
+                UpdatedFile::Merged(PathBuf::from("src/main.rs")),
+            ]
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("src/main.rs")).unwrap(),
+            "// my header\nfn main() {\n    println!(\"demo\");\n    run();\n}\n"
+        );
+        let lib = std::fs::read_to_string(dir.join("src/lib.rs")).unwrap();
+        assert!(
+            lib.contains("<<<<<<<") && lib.contains("pub mod mine;") && lib.contains("pub mod b;")
+        );
+        assert!(!dir.join("old.txt").exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.join("new.txt")).unwrap(),
+            "new\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("notes.txt")).unwrap(),
+            "my notes\n"
+        );
+        assert!(manifest.files.contains_key(Path::new("new.txt")));
+        assert!(!manifest.files.contains_key(Path::new("old.txt")));
+        assert!(!manifest.files.contains_key(Path::new("notes.txt")));
+        assert_eq!(
+            manifest.files[Path::new("src/main.rs")],
+            Manifest::checksum(std::fs::read(dir.join("src/main.rs")).unwrap().as_slice())
+        );
+        assert_eq!(manifest.files[Path::new("src/lib.rs")], lib_checksum);
+        assert_eq!(
+            manifest.conflicts,
+            BTreeSet::from([PathBuf::from("src/lib.rs")])
+        );
+
+        // The next update refuses to run until the conflict markers are gone
+        assert!(update_command.resolve_conflicts(&mut manifest).is_err());
+        let resolved = "// lib\npub mod mine;\npub mod b;\n// end\n";
+        std::fs::write(dir.join("src/lib.rs"), resolved).unwrap();
+        assert!(update_command.resolve_conflicts(&mut manifest).unwrap());
+        assert!(manifest.conflicts.is_empty());
+        assert_eq!(
+            manifest.files[Path::new("src/lib.rs")],
+            Manifest::checksum(resolved.as_bytes())
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
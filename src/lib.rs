@@ -2,6 +2,13 @@ mod cli;
 mod utils;
 
 pub mod prelude {
-    pub use crate::cli::{Cli, Commands, CreateCommand, Templates, TemplatesCommand};
-    pub use crate::utils::{Config, Template};
+    pub use crate::cli::{
+        Cli, Commands, ConflictPolicy, CreateCommand, DiffCommand, GenerateCommand,
+        GitignoreCommand, Gitignores, RunCommand, Templates, TemplatesCommand, UpdateCommand,
+    };
+    pub use crate::utils::{
+        Config, GitConfig, GitignoreConfig, Manifest, MessageKind, Output, OutputFormat,
+        PlainReporter, Prompter, Reporter, SilentReporter, Style, TaskFailed, TaskFile, Template,
+        TextReporter, Theme, Verbosity,
+    };
 }
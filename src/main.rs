@@ -3,35 +3,189 @@ use scaffer::prelude::*;
 
 /// The main entry point of the application
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let config = Config::load()?;
-
     let cli = Cli::parse();
+    let format = match cli.commands {
+        Commands::Diff { json: true, .. } => OutputFormat::Json,
+        _ => cli.output,
+    };
+    let output = Output::new(format).with_verbosity(cli.verbosity());
+    let command = cli.commands.name();
+
+    match run(cli, &output) {
+        Err(error) if error.is::<TaskFailed>() => {
+            if !output.is_json() {
+                output.failure(&error.to_string());
+            }
+            let code = error
+                .downcast_ref::<TaskFailed>()
+                .map_or(1, |failed| failed.code);
+            std::process::exit(code);
+        }
+        Err(error) if output.is_json() => {
+            output.error(command, error.as_ref());
+            std::process::exit(1);
+        }
+        result => result,
+    }
+}
+
+/// Run the command given on the command line
+///
+/// # Arguments
+///
+/// * `cli` - The parsed command line
+/// * `output` - The output to report the progress and the result to
+///
+/// # Returns
+///
+/// * `Result<(), Box<dyn std::error::Error>>` - The result of the command
+fn run(cli: Cli, output: &Output) -> Result<(), Box<dyn std::error::Error>> {
+    let config = Config::load()?;
+    let theme = Theme::from_config(&config)?;
+    inquire::set_global_render_config(theme.render_config());
+    let output = &output.clone().with_theme(theme);
+    let prompter = Prompter::new(cli.yes, cli.no_input);
 
     match cli.commands {
         Commands::Create {
             language,
             name,
             path,
+            on_conflict,
+            force,
+            no_hooks,
+            git,
+            gitignore,
+            license,
+            author,
+            description,
+            members,
+            run,
         } => {
-            let mut create_command = CreateCommand::default();
+            let mut create_command = CreateCommand {
+                conflict_policy: if force {
+                    ConflictPolicy::Overwrite
+                } else {
+                    on_conflict
+                },
+                prompter,
+                output: output.clone(),
+                run_hooks: !no_hooks,
+                init_git: git,
+                gitignore,
+                license,
+                author,
+                description,
+                members,
+                run,
+                ..Default::default()
+            };
             create_command.run_create(language, name, path, &config)?;
         }
+        Commands::Generate {
+            generator,
+            name,
+            language,
+            path,
+            force,
+        } => {
+            let mut generate_command = GenerateCommand {
+                language,
+                force,
+                prompter,
+                output: output.clone(),
+                ..Default::default()
+            };
+            if let Some(path) = path {
+                generate_command.project_path = path;
+            }
+            generate_command.run_generate(generator, name, &config)?;
+        }
+        Commands::Update { path } => {
+            let mut update_command = UpdateCommand {
+                output: output.clone(),
+                ..Default::default()
+            };
+            if let Some(path) = path {
+                update_command.project_path = path;
+            }
+            update_command.run_update(&config)?;
+        }
+        Commands::Diff {
+            path,
+            language,
+            vars,
+            stat,
+            json: _,
+        } => {
+            let mut diff_command = DiffCommand {
+                language,
+                variables: vars,
+                stat,
+                output: output.clone(),
+                ..Default::default()
+            };
+            if let Some(path) = path {
+                diff_command.project_path = path;
+            }
+            diff_command.run_diff(&config)?;
+        }
+        Commands::Run { task, path, args } => {
+            let mut run_command = RunCommand {
+                output: output.clone(),
+                ..Default::default()
+            };
+            if let Some(path) = path {
+                run_command.project_path = path;
+            }
+            run_command.run_task(task, args)?;
+        }
         Commands::Templates { subcommand } => match subcommand {
             TemplatesCommand::List { filter } => {
                 let templates = Templates::list_templates(filter, &config)?;
-                println!("Avaiable templates:");
-                for template in templates {
-                    println!("- {}", template);
+                output.print("Avaiable templates:");
+                for template in &templates {
+                    output.print(format!("- {}", template));
                 }
+                output.result(
+                    "templates list",
+                    serde_json::json!({ "templates": templates }),
+                )?;
             }
-            TemplatesCommand::Add { language } => {
-                Templates::add_template(language)?;
+            TemplatesCommand::Add {
+                language,
+                start_command,
+                directories,
+            } => {
+                Templates::add_template(language, start_command, directories, &prompter, output)?;
             }
             TemplatesCommand::Remove { template } => {
-                Templates::remove_template(template)?;
+                Templates::remove_template(template, &prompter, output)?;
             }
             TemplatesCommand::Update { language } => {
-                Templates::update_template(language)?;
+                Templates::update_template(language, &prompter, output)?;
+            }
+            TemplatesCommand::Install {
+                source,
+                trust_hooks,
+            } => {
+                Templates::install_template(source, trust_hooks, &prompter, output, &config)?;
+            }
+        },
+        Commands::Gitignore { subcommand } => match subcommand {
+            GitignoreCommand::List => {
+                let gitignores = Gitignores::list_gitignores(&config)?;
+                output.print("Available .gitignore templates:");
+                for gitignore in &gitignores {
+                    output.print(format!("- {}", gitignore));
+                }
+                output.result(
+                    "gitignore list",
+                    serde_json::json!({ "gitignores": gitignores }),
+                )?;
+            }
+            GitignoreCommand::Update { names } => {
+                Gitignores::update_gitignores(names, &config, output)?;
             }
         },
     }
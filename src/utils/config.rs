@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{collections::BTreeMap, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 
@@ -7,9 +7,78 @@ pub struct Config {
     pub template_dir: PathBuf,
     pub languages: Vec<String>,
     pub theme: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub git: GitConfig,
+    #[serde(default)]
+    pub gitignore: GitignoreConfig,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub themes: BTreeMap<String, toml::Table>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GitConfig {
+    pub default_branch: Option<String>,
+    pub author_name: Option<String>,
+    pub author_email: Option<String>,
+    pub commit_message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct GitignoreConfig {
+    pub base_url: Option<String>,
+    pub local_dir: Option<PathBuf>,
+}
+
+impl GitConfig {
+    /// The message of the initial commit
+    ///
+    /// # Returns
+    ///
+    /// The configured commit message or the default one
+    pub fn commit_message(&self) -> String {
+        self.commit_message
+            .clone()
+            .unwrap_or_else(|| "Initial commit".to_string())
+    }
 }
 
 impl Config {
+    /// Create the default configuration with the given template directory
+    ///
+    /// # Arguments
+    ///
+    /// * `template_dir` - The directory of the installed templates
+    ///
+    /// # Returns
+    ///
+    /// The configuration object
+    pub fn new(template_dir: PathBuf) -> Self {
+        let default_languages = vec![
+            "Rust".to_string(),
+            "Python".to_string(),
+            "Java".to_string(),
+            "PHP".to_string(),
+            "C".to_string(),
+            "C++".to_string(),
+            "HTML".to_string(),
+            "Go".to_string(),
+            "Workspace".to_string(),
+            "Fullstack".to_string(),
+        ];
+
+        Config {
+            template_dir,
+            languages: default_languages,
+            theme: "default".to_string(),
+            author: None,
+            git: GitConfig::default(),
+            gitignore: GitignoreConfig::default(),
+            themes: BTreeMap::new(),
+        }
+    }
+
     /// Load the configuration from the config file
     ///
     /// # Returns
@@ -51,6 +120,28 @@ impl Config {
         Ok(config_dir)
     }
 
+    /// Get the directory of the cached gitignore templates
+    ///
+    /// # Returns
+    ///
+    /// The directory of the cached gitignore templates
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the configuration directory cannot be found
+    pub fn gitignore_cache_dir() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        Ok(Self::get_config_dir()?.join("gitignore"))
+    }
+
+    /// Get the directory of the templates bundled with scaffer
+    ///
+    /// # Returns
+    ///
+    /// The directory of the bundled templates
+    pub fn bundled_template_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("templates")
+    }
+
     /// Save the configuration to the config file
     ///
     /// # Errors
@@ -78,29 +169,12 @@ impl Config {
             std::fs::create_dir(&template_dir)?;
         }
 
-        let default_languages = vec![
-            "Rust".to_string(),
-            "Python".to_string(),
-            "Java".to_string(),
-            "PHP".to_string(),
-            "C".to_string(),
-            "C++".to_string(),
-            "HTML".to_string(),
-            "Go".to_string(),
-        ];
-
-        let config = Config {
-            template_dir: template_dir.clone(),
-            languages: default_languages,
-            theme: "default".to_string(),
-        };
+        let config = Config::new(template_dir.clone());
 
         let config = toml::to_string(&config)?;
         std::fs::write(config_file, config)?;
 
-        let default_templates_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("templates");
-
-        for entry in std::fs::read_dir(default_templates_path)? {
+        for entry in std::fs::read_dir(Self::bundled_template_dir())? {
             let entry = entry?;
             let path = entry.path();
             let file_name = path.file_name().unwrap().to_str().unwrap();
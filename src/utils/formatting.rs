@@ -0,0 +1,522 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+const EDITORCONFIG_FILE: &str = ".editorconfig";
+
+/// The line ending of a generated file
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    Lf,
+    Crlf,
+}
+
+/// The indentation of a generated file
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IndentStyle {
+    Tab,
+    Space,
+}
+
+/// How the content of a generated file is normalized
+///
+/// Unset settings leave the content as it is, except for the line ending which is LF unless
+/// configured otherwise.
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Formatting {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line_ending: Option<LineEnding>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub final_newline: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indent_style: Option<IndentStyle>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indent_size: Option<usize>,
+}
+
+impl Formatting {
+    /// Check if no setting is set
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the formatting is the default one
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Combine the formatting with another one that takes precedence
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The formatting whose set settings override the ones of `self`
+    ///
+    /// # Returns
+    ///
+    /// * `Formatting` - The combined formatting
+    pub fn merge(&self, other: &Formatting) -> Formatting {
+        Formatting {
+            line_ending: other.line_ending.or(self.line_ending),
+            final_newline: other.final_newline.or(self.final_newline),
+            indent_style: other.indent_style.or(self.indent_style),
+            indent_size: other.indent_size.or(self.indent_size),
+        }
+    }
+
+    /// Normalize the line endings, the final newline and the indentation of the content
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The content to normalize
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The normalized content
+    pub fn apply(&self, content: &str) -> String {
+        let eol = match self.line_ending {
+            Some(LineEnding::Crlf) => "\r\n",
+            _ => "\n",
+        };
+        let indent_size = self.indent_size.filter(|size| *size > 0).unwrap_or(4);
+
+        let mut normalized = content
+            .lines()
+            .map(|line| match self.indent_style {
+                Some(style) => reindent(line, style, indent_size),
+                None => line.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(eol);
+
+        match self.final_newline.unwrap_or(content.ends_with('\n')) {
+            true if !normalized.is_empty() => normalized.push_str(eol),
+            true => {}
+            false => normalized.truncate(normalized.trim_end_matches(['\r', '\n']).len()),
+        }
+
+        normalized
+    }
+}
+
+/// Check if the indentation of a file is part of its syntax, so it must not be converted
+///
+/// Makefiles need tabs before their recipes and YAML does not allow tabs for indentation.
+///
+/// # Arguments
+///
+/// * `path` - The path of the file
+///
+/// # Returns
+///
+/// * `bool` - Whether the file is a Makefile or a YAML file
+pub fn has_significant_indentation(path: &Path) -> bool {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    matches!(file_name.as_str(), "makefile" | "gnumakefile")
+        || matches!(extension.as_str(), "mk" | "yml" | "yaml")
+}
+
+/// Replace the leading whitespace of a line by tabs or spaces of the same width
+///
+/// # Arguments
+///
+/// * `line` - The line to reindent
+/// * `style` - The indentation to use
+/// * `size` - The width of a tab and of an indentation level
+///
+/// # Returns
+///
+/// * `String` - The reindented line
+fn reindent(line: &str, style: IndentStyle, size: usize) -> String {
+    let content = line.trim_start_matches([' ', '\t']);
+    let width = line[..line.len() - content.len()]
+        .chars()
+        .fold(0, |width, c| match c {
+            '\t' => (width / size + 1) * size,
+            _ => width + 1,
+        });
+
+    let indentation = match style {
+        IndentStyle::Space => " ".repeat(width),
+        IndentStyle::Tab => "\t".repeat(width / size) + &" ".repeat(width % size),
+    };
+
+    indentation + content
+}
+
+/// The formatting settings of the `.editorconfig` files that apply to a project
+///
+/// See <https://editorconfig.org> for the format. The properties `end_of_line`,
+/// `insert_final_newline`, `indent_style`, `indent_size` and `tab_width` are supported.
+#[derive(Debug, Clone, Default)]
+pub struct EditorConfig {
+    sections: Vec<Section>,
+}
+
+/// A section of an `.editorconfig` file
+#[derive(Debug, Clone)]
+struct Section {
+    directory: PathBuf,
+    pattern: String,
+    formatting: Formatting,
+}
+
+impl EditorConfig {
+    /// Load the `.editorconfig` files of the project directory and its parents
+    ///
+    /// The search stops at the first file that declares `root = true`. Directories that do not
+    /// exist yet are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_path` - The root directory of the project
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, Box<dyn std::error::Error>>` - The settings of all found files
+    ///
+    /// # Errors
+    ///
+    /// * If an existing `.editorconfig` file cannot be read
+    pub fn load(project_path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let project_path = std::path::absolute(project_path)?;
+
+        let mut files = vec![];
+        for directory in project_path.ancestors() {
+            let path = directory.join(EDITORCONFIG_FILE);
+            if !path.is_file() {
+                continue;
+            }
+
+            let content = std::fs::read_to_string(&path)?;
+            let root = is_root(&content);
+            files.push((directory.to_path_buf(), content));
+            if root {
+                break;
+            }
+        }
+
+        let mut editorconfig = Self::default();
+        for (directory, content) in files.into_iter().rev() {
+            editorconfig.add(&directory, &content);
+        }
+
+        Ok(editorconfig)
+    }
+
+    /// Add the sections of an `.editorconfig` file that take precedence over the loaded ones
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - The directory of the file, the patterns are relative to it
+    /// * `content` - The content of the file
+    pub fn add(&mut self, directory: &Path, content: &str) {
+        let directory = std::path::absolute(directory).unwrap_or_else(|_| directory.to_path_buf());
+        if is_root(content) {
+            self.sections.clear();
+        }
+
+        let first_section = self.sections.len();
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with(['#', ';']) {
+                continue;
+            }
+
+            if let Some(pattern) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                self.sections.push(Section {
+                    directory: directory.clone(),
+                    pattern: pattern.to_string(),
+                    formatting: Formatting::default(),
+                });
+                continue;
+            }
+
+            // Properties before the first section (like `root`) do not belong to a section
+            let section = match self.sections.len() > first_section {
+                true => self.sections.last_mut(),
+                false => None,
+            };
+            let (Some(section), Some((key, value))) = (section, line.split_once('=')) else {
+                continue;
+            };
+
+            let value = value.trim().to_lowercase();
+            let formatting = &mut section.formatting;
+            match key.trim().to_lowercase().as_str() {
+                "end_of_line" => {
+                    formatting.line_ending = match value.as_str() {
+                        "lf" => Some(LineEnding::Lf),
+                        "crlf" => Some(LineEnding::Crlf),
+                        _ => None,
+                    }
+                }
+                "insert_final_newline" => formatting.final_newline = value.parse().ok(),
+                "indent_style" => {
+                    formatting.indent_style = match value.as_str() {
+                        "tab" => Some(IndentStyle::Tab),
+                        "space" => Some(IndentStyle::Space),
+                        _ => None,
+                    }
+                }
+                "indent_size" if value != "tab" => formatting.indent_size = value.parse().ok(),
+                "tab_width" if formatting.indent_size.is_none() => {
+                    formatting.indent_size = value.parse().ok()
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The formatting of the sections whose pattern matches the file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file
+    ///
+    /// # Returns
+    ///
+    /// * `Formatting` - The combined formatting, later sections take precedence
+    pub fn formatting(&self, path: &Path) -> Formatting {
+        let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+
+        self.sections
+            .iter()
+            .filter(|section| {
+                path.strip_prefix(&section.directory)
+                    .is_ok_and(|relative| matches_section(&section.pattern, relative))
+            })
+            .fold(Formatting::default(), |formatting, section| {
+                formatting.merge(&section.formatting)
+            })
+    }
+}
+
+/// Check if an `.editorconfig` file declares `root = true` before its first section
+///
+/// # Arguments
+///
+/// * `content` - The content of the file
+///
+/// # Returns
+///
+/// * `bool` - Whether the file is the root file
+fn is_root(content: &str) -> bool {
+    content
+        .lines()
+        .map(str::trim)
+        .take_while(|line| !line.starts_with('['))
+        .filter_map(|line| line.split_once('='))
+        .any(|(key, value)| {
+            key.trim().eq_ignore_ascii_case("root") && value.trim().eq_ignore_ascii_case("true")
+        })
+}
+
+/// Check if the pattern of a section matches a file
+///
+/// Patterns without a `/` match the file name in any directory, others match the path
+/// relative to the `.editorconfig` file.
+///
+/// # Arguments
+///
+/// * `pattern` - The pattern of the section (e.g. `*.{js,py}`, `src/**.rs`)
+/// * `relative` - The path of the file relative to the `.editorconfig` file
+///
+/// # Returns
+///
+/// * `bool` - Whether the section applies to the file
+fn matches_section(pattern: &str, relative: &Path) -> bool {
+    let relative = relative.to_string_lossy().replace('\\', "/");
+    let file_name = relative.rsplit('/').next().unwrap_or_default();
+
+    expand_braces(pattern).iter().any(|pattern| {
+        let (pattern, path) = match pattern.contains('/') {
+            true => (pattern.trim_start_matches('/'), relative.as_str()),
+            false => (pattern.as_str(), file_name),
+        };
+        let pattern: Vec<char> = pattern.chars().collect();
+        let path: Vec<char> = path.chars().collect();
+
+        glob_match(&pattern, &path)
+    })
+}
+
+/// Expand the `{a,b}` alternatives of a pattern
+///
+/// # Arguments
+///
+/// * `pattern` - The pattern to expand
+///
+/// # Returns
+///
+/// * `Vec<String>` - The patterns without alternatives
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some(start) = pattern.find('{') else {
+        return vec![pattern.to_string()];
+    };
+
+    let mut depth = 0;
+    let mut alternatives = vec![];
+    let mut from = start + 1;
+    for (index, c) in pattern
+        .char_indices()
+        .skip_while(|(index, _)| *index <= start)
+    {
+        match c {
+            '{' => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            ',' if depth == 0 => {
+                alternatives.push(&pattern[from..index]);
+                from = index + 1;
+            }
+            '}' => {
+                alternatives.push(&pattern[from..index]);
+                if alternatives.len() == 1 {
+                    break;
+                }
+
+                let (prefix, suffix) = (&pattern[..start], &pattern[index + 1..]);
+                return alternatives
+                    .iter()
+                    .flat_map(|alternative| {
+                        expand_braces(&format!("{}{}{}", prefix, alternative, suffix))
+                    })
+                    .collect();
+            }
+            _ => {}
+        }
+    }
+
+    vec![pattern.to_string()]
+}
+
+/// Match a path against a glob pattern with `*`, `**`, `?` and `[...]`
+///
+/// # Arguments
+///
+/// * `pattern` - The characters of the pattern
+/// * `path` - The characters of the path
+///
+/// # Returns
+///
+/// * `bool` - Whether the whole path matches
+fn glob_match(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        ['*', '*', rest @ ..] => (0..=path.len()).any(|i| glob_match(rest, &path[i..])),
+        ['*', rest @ ..] => (0..=path.len())
+            .take_while(|&i| i == 0 || path[i - 1] != '/')
+            .any(|i| glob_match(rest, &path[i..])),
+        ['?', rest @ ..] => {
+            matches!(path.first(), Some(c) if *c != '/') && glob_match(rest, &path[1..])
+        }
+        ['[', class @ ..] if class.contains(&']') => {
+            let end = class.iter().position(|c| *c == ']').unwrap_or_default();
+            let Some(c) = path.first() else {
+                return false;
+            };
+            let (negated, class_chars) = match &class[..end] {
+                ['!', chars @ ..] => (true, chars),
+                chars => (false, chars),
+            };
+
+            let mut matched = false;
+            let mut i = 0;
+            while i < class_chars.len() {
+                if i + 2 < class_chars.len() && class_chars[i + 1] == '-' {
+                    matched |= (class_chars[i]..=class_chars[i + 2]).contains(c);
+                    i += 3;
+                } else {
+                    matched |= class_chars[i] == *c;
+                    i += 1;
+                }
+            }
+
+            matched != negated && glob_match(&class[end + 1..], &path[1..])
+        }
+        ['\\', c, rest @ ..] | [c, rest @ ..] => {
+            path.first() == Some(c) && glob_match(rest, &path[1..])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_temp_dir;
+
+    #[test]
+    fn test_apply_formatting() {
+        let formatting = Formatting {
+            line_ending: Some(LineEnding::Crlf),
+            final_newline: Some(true),
+            indent_style: Some(IndentStyle::Tab),
+            indent_size: Some(4),
+        };
+        assert_eq!(
+            formatting.apply("fn main() {\n        run();\n  \tx\n}"),
+            "fn main() {\r\n\t\trun();\r\n\tx\r\n}\r\n"
+        );
+
+        let formatting = Formatting {
+            indent_style: Some(IndentStyle::Space),
+            indent_size: Some(2),
+            final_newline: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(formatting.apply("a\r\n\tb\n\n"), "a\n  b");
+        assert_eq!(Formatting::default().apply("a\r\nb\n"), "a\nb\n");
+        assert_eq!(Formatting::default().apply("a"), "a");
+    }
+
+    #[test]
+    fn test_editorconfig() {
+        let dir = create_temp_dir("scaffer_test_editorconfig").unwrap();
+        std::fs::create_dir_all(dir.join("project")).unwrap();
+        std::fs::write(
+            dir.join(EDITORCONFIG_FILE),
+            "root = true\n\n[*]\nend_of_line = crlf\ninsert_final_newline = true\n\n[*.{rs,toml}]\nindent_style = space\nindent_size = 4\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("project").join(EDITORCONFIG_FILE),
+            "[Makefile]\nindent_style = tab\n\n[src/**]\nend_of_line = lf\n",
+        )
+        .unwrap();
+
+        let editorconfig = EditorConfig::load(&dir.join("project")).unwrap();
+
+        let formatting = editorconfig.formatting(&dir.join("project/src/bin/main.rs"));
+        assert_eq!(formatting.line_ending, Some(LineEnding::Lf));
+        assert_eq!(formatting.final_newline, Some(true));
+        assert_eq!(formatting.indent_style, Some(IndentStyle::Space));
+        assert_eq!(formatting.indent_size, Some(4));
+
+        let formatting = editorconfig.formatting(&dir.join("project/Makefile"));
+        assert_eq!(formatting.line_ending, Some(LineEnding::Crlf));
+        assert_eq!(formatting.indent_style, Some(IndentStyle::Tab));
+
+        let formatting = editorconfig.formatting(&dir.join("project/README.md"));
+        assert_eq!(formatting.indent_style, None);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_matches_section() {
+        assert!(matches_section("*.rs", Path::new("src/main.rs")));
+        assert!(matches_section("*.{js,py}", Path::new("app.py")));
+        assert!(matches_section("src/*.rs", Path::new("src/main.rs")));
+        assert!(!matches_section("src/*.rs", Path::new("src/bin/main.rs")));
+        assert!(matches_section("/src/**.rs", Path::new("src/bin/main.rs")));
+        assert!(matches_section("[Mm]akefile", Path::new("makefile")));
+        assert!(!matches_section("*.[!r]s", Path::new("main.rs")));
+    }
+}
@@ -0,0 +1,175 @@
+use std::{path::Path, process::Command};
+
+use super::GitConfig;
+
+/// Initialize a git repository in the given directory and create an initial commit
+///
+/// # Arguments
+///
+/// * `path` - The directory of the repository
+/// * `config` - The git configuration with the default branch and the author identity
+///
+/// # Returns
+///
+/// * `Result<(), Box<dyn std::error::Error>>` - The result of the initialization
+///
+/// # Errors
+///
+/// * If git is not installed
+/// * If a git command fails, e.g. because no author identity is configured
+pub fn init_repository(path: &Path, config: &GitConfig) -> Result<(), Box<dyn std::error::Error>> {
+    run_git(path, config, &["init", "--quiet"])?;
+
+    if let Some(branch) = &config.default_branch {
+        run_git(
+            path,
+            config,
+            &["symbolic-ref", "HEAD", &format!("refs/heads/{}", branch)],
+        )?;
+    }
+
+    run_git(path, config, &["add", "--all"])?;
+    run_git(
+        path,
+        config,
+        &["commit", "--quiet", "--message", &config.commit_message()],
+    )?;
+
+    Ok(())
+}
+
+/// Clone a repository and check out the given reference
+///
+/// # Arguments
+///
+/// * `repository` - The URL or local path of the repository
+/// * `reference` - The branch, tag or commit to check out, the default branch if omitted
+/// * `path` - The directory to clone the repository into, it must not exist yet
+///
+/// # Returns
+///
+/// * `Result<String, Box<dyn std::error::Error>>` - The commit that has been checked out
+///
+/// # Errors
+///
+/// * If git is not installed
+/// * If the repository cannot be cloned or the reference does not exist
+pub fn clone_repository(
+    repository: &str,
+    reference: Option<&str>,
+    path: &Path,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let config = GitConfig::default();
+    let parent = path.parent().ok_or("Invalid clone path")?;
+    run_git(
+        parent,
+        &config,
+        &[
+            "clone",
+            "--quiet",
+            "--",
+            repository,
+            &path.to_string_lossy(),
+        ],
+    )?;
+
+    if let Some(reference) = reference {
+        run_git(path, &config, &["checkout", "--quiet", reference, "--"])?;
+    }
+
+    Ok(run_git(path, &config, &["rev-parse", "HEAD"])?
+        .trim()
+        .to_string())
+}
+
+/// The user name configured in git
+///
+/// # Returns
+///
+/// The value of `git config user.name` if git is installed and the name is set
+pub fn configured_user_name() -> Option<String> {
+    let output = Command::new("git")
+        .args(["config", "user.name"])
+        .output()
+        .ok()?;
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    (output.status.success() && !name.is_empty()).then_some(name)
+}
+
+/// Run a git command in the given directory
+///
+/// The author identity of the configuration is passed to git if it is set.
+///
+/// # Arguments
+///
+/// * `path` - The directory of the repository
+/// * `config` - The git configuration
+/// * `args` - The arguments of the git command
+///
+/// # Returns
+///
+/// * `Result<String, Box<dyn std::error::Error>>` - The standard output of the git command
+fn run_git(
+    path: &Path,
+    config: &GitConfig,
+    args: &[&str],
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut command = Command::new("git");
+    command.current_dir(path);
+
+    if let Some(name) = &config.author_name {
+        command.arg("-c").arg(format!("user.name={}", name));
+    }
+    if let Some(email) = &config.author_email {
+        command.arg("-c").arg(format!("user.email={}", email));
+    }
+
+    let output = command
+        .args(args)
+        .output()
+        .map_err(|err| format!("Could not run git: {}", err))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_temp_dir;
+
+    #[test]
+    fn test_init_repository() {
+        let dir = create_temp_dir("scaffer_test_git_init").unwrap();
+        std::fs::write(dir.join("main.rs"), "fn main() {}").unwrap();
+
+        let config = GitConfig {
+            default_branch: Some("trunk".to_string()),
+            author_name: Some("Scaffer".to_string()),
+            author_email: Some("scaffer@example.com".to_string()),
+            commit_message: None,
+        };
+        init_repository(&dir, &config).unwrap();
+
+        let output = Command::new("git")
+            .current_dir(&dir)
+            .args(["log", "--format=%an <%ae> %s", "trunk"])
+            .output()
+            .unwrap();
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout).trim(),
+            "Scaffer <scaffer@example.com> Initial commit"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,403 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+use super::{Config, Output};
+
+/// The gitignore templates that are bundled into the binary
+const BUNDLED_GITIGNORES: &[(&str, &str)] = &[
+    ("C", include_str!("../../gitignore/C.gitignore")),
+    ("C++", include_str!("../../gitignore/C++.gitignore")),
+    (
+        "Composer",
+        include_str!("../../gitignore/Composer.gitignore"),
+    ),
+    ("Go", include_str!("../../gitignore/Go.gitignore")),
+    ("Java", include_str!("../../gitignore/Java.gitignore")),
+    (
+        "JetBrains",
+        include_str!("../../gitignore/JetBrains.gitignore"),
+    ),
+    ("Linux", include_str!("../../gitignore/Linux.gitignore")),
+    ("macOS", include_str!("../../gitignore/macOS.gitignore")),
+    ("Python", include_str!("../../gitignore/Python.gitignore")),
+    ("Rust", include_str!("../../gitignore/Rust.gitignore")),
+    (
+        "VisualStudioCode",
+        include_str!("../../gitignore/VisualStudioCode.gitignore"),
+    ),
+    ("Windows", include_str!("../../gitignore/Windows.gitignore")),
+];
+
+/// The default base URL of the gitignore templates on GitHub
+const DEFAULT_GITIGNORE_BASE_URL: &str =
+    "https://raw.githubusercontent.com/github/gitignore/refs/heads/main";
+
+/// The places the gitignore templates are loaded from
+///
+/// Templates are looked up in the configured local directory first, then in the cache
+/// filled by `scaffer gitignore update` and finally in the bundled templates.
+#[derive(Debug, Clone)]
+pub struct GitignoreSource {
+    pub local_dir: Option<PathBuf>,
+    pub cache_dir: PathBuf,
+    pub base_url: String,
+}
+
+impl GitignoreSource {
+    /// Create the gitignore source from the configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration object
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, Box<dyn std::error::Error>>` - The gitignore source
+    ///
+    /// # Errors
+    ///
+    /// * If the configuration directory cannot be found
+    pub fn from_config(config: &Config) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            local_dir: config.gitignore.local_dir.clone(),
+            cache_dir: Config::gitignore_cache_dir()?,
+            base_url: config
+                .gitignore
+                .base_url
+                .clone()
+                .unwrap_or_else(|| DEFAULT_GITIGNORE_BASE_URL.to_string()),
+        })
+    }
+
+    /// Load the gitignore template with the given name without going to the network
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the gitignore template (e.g. Rust, Python)
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, Box<dyn std::error::Error>>` - The content of the gitignore template
+    ///
+    /// # Errors
+    ///
+    /// * If the name is not a valid gitignore template name
+    /// * If there is no gitignore template with the given name
+    pub fn load(&self, name: &str) -> Result<String, Box<dyn std::error::Error>> {
+        Self::validate_name(name)?;
+
+        for dir in self.local_dir.iter().chain([&self.cache_dir]) {
+            if let Some(path) = Self::find_in_dir(dir, name) {
+                return Ok(std::fs::read_to_string(path)?);
+            }
+        }
+
+        BUNDLED_GITIGNORES
+            .iter()
+            .find(|(bundled, _)| bundled.eq_ignore_ascii_case(name))
+            .map(|(_, content)| content.to_string())
+            .ok_or_else(|| format!("No gitignore template found for {}", name).into())
+    }
+
+    /// Merge the gitignore templates with the given names into one .gitignore file
+    ///
+    /// Every template gets its own section, patterns that already appeared in an earlier
+    /// section are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - The names of the gitignore templates (e.g. Rust, JetBrains, macOS)
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, Box<dyn std::error::Error>>` - The merged .gitignore content
+    ///
+    /// # Errors
+    ///
+    /// * If one of the gitignore templates cannot be found
+    pub fn merge(&self, names: &[String]) -> Result<String, Box<dyn std::error::Error>> {
+        let mut seen_names = HashSet::new();
+        let mut seen_patterns = HashSet::new();
+        let mut missing = vec![];
+        let mut sections = vec![];
+
+        for name in names {
+            if !seen_names.insert(name.to_lowercase()) {
+                continue;
+            }
+
+            let gitignore = match self.load(name) {
+                Ok(gitignore) => gitignore,
+                Err(_) => {
+                    missing.push(name.clone());
+                    continue;
+                }
+            };
+
+            let lines: Vec<&str> = gitignore
+                .lines()
+                .filter(|line| {
+                    let pattern = line.trim();
+                    pattern.is_empty()
+                        || pattern.starts_with('#')
+                        || seen_patterns.insert(pattern.to_string())
+                })
+                .collect();
+
+            sections.push(format!("### {} ###\n{}", name, lines.join("\n").trim()));
+        }
+
+        if !missing.is_empty() {
+            return Err(format!("No gitignore template found for {}", missing.join(", ")).into());
+        }
+
+        Ok(sections.join("\n\n") + "\n")
+    }
+
+    /// Fetch the gitignore template with the given name from the base URL and store it in the cache
+    ///
+    /// Templates are looked up in the root of the base URL first and then in `Global`
+    /// (e.g. editors and operating systems), like in the github/gitignore repository.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the gitignore template (e.g. Rust, Python)
+    /// * `output` - The output to report the requests to
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, Box<dyn std::error::Error>>` - The content of the fetched gitignore template
+    ///
+    /// # Errors
+    ///
+    /// * If the name is not a valid gitignore template name
+    /// * If the gitignore template cannot be fetched
+    /// * If the gitignore template cannot be written to the cache
+    pub fn fetch(&self, name: &str, output: &Output) -> Result<String, Box<dyn std::error::Error>> {
+        Self::validate_name(name)?;
+
+        let base_url = self.base_url.trim_end_matches('/');
+        let encoded_name = Self::encode_name(name);
+        let get = |url: String| {
+            output.detail(&format!("GET {}", url));
+            ureq::get(&url)
+        };
+
+        let gitignore = match get(format!("{}/{}.gitignore", base_url, encoded_name)).call() {
+            Ok(response) => response.into_string()?,
+            Err(ureq::Error::Status(404, _)) => {
+                get(format!("{}/Global/{}.gitignore", base_url, encoded_name))
+                    .call()?
+                    .into_string()?
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        std::fs::create_dir_all(&self.cache_dir)?;
+        std::fs::write(
+            self.cache_dir.join(format!("{}.gitignore", name)),
+            &gitignore,
+        )?;
+
+        Ok(gitignore)
+    }
+
+    /// List the names of all available gitignore templates
+    ///
+    /// # Returns
+    ///
+    /// The sorted names of the local, cached and bundled gitignore templates
+    pub fn available(&self) -> Vec<String> {
+        let mut names: Vec<String> = BUNDLED_GITIGNORES
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .collect();
+
+        for dir in self.local_dir.iter().chain([&self.cache_dir]) {
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                if let Some(name) = file_name.strip_suffix(".gitignore") {
+                    if !names.iter().any(|known| known.eq_ignore_ascii_case(name)) {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+        }
+
+        names.sort();
+        names
+    }
+
+    /// Find the gitignore template with the given name in a directory, ignoring the case
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory to search
+    /// * `name` - The name of the gitignore template
+    ///
+    /// # Returns
+    ///
+    /// The path of the gitignore template if it exists
+    fn find_in_dir(dir: &Path, name: &str) -> Option<PathBuf> {
+        let file_name = format!("{}.gitignore", name);
+
+        let exact = dir.join(&file_name);
+        if exact.exists() {
+            return Some(exact);
+        }
+
+        std::fs::read_dir(dir)
+            .ok()?
+            .flatten()
+            .find(|entry| {
+                entry
+                    .file_name()
+                    .to_string_lossy()
+                    .eq_ignore_ascii_case(&file_name)
+            })
+            .map(|entry| entry.path())
+    }
+
+    /// Check that the name of a gitignore template can be used as a file name
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the gitignore template
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Box<dyn std::error::Error>>` - Ok if the name is valid
+    ///
+    /// # Errors
+    ///
+    /// * If the name is empty, contains a path separator or is a relative path component
+    fn validate_name(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if name.is_empty() || name.contains(['/', '\\']) || name.contains("..") || name == "." {
+            return Err(format!("Invalid gitignore template name: {}", name).into());
+        }
+
+        Ok(())
+    }
+
+    /// Percent-encode the name of a gitignore template for the use in a URL
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the gitignore template
+    ///
+    /// # Returns
+    ///
+    /// The encoded name
+    fn encode_name(name: &str) -> String {
+        name.bytes()
+            .map(|byte| {
+                if byte.is_ascii_alphanumeric() || b"-_.".contains(&byte) {
+                    (byte as char).to_string()
+                } else {
+                    format!("%{:02X}", byte)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{create_temp_dir, OutputFormat};
+
+    fn source(name: &str) -> GitignoreSource {
+        GitignoreSource {
+            local_dir: None,
+            cache_dir: create_temp_dir(name).unwrap(),
+            base_url: DEFAULT_GITIGNORE_BASE_URL.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_load_bundled_gitignore() {
+        let source = source("scaffer_test_gitignore_empty");
+
+        let gitignore = source.load("rust").unwrap();
+        assert!(gitignore.contains("target"));
+
+        assert!(source.load("Unknown").is_err());
+
+        std::fs::remove_dir_all(&source.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_merge_gitignores() {
+        let source = source("scaffer_test_gitignore_empty");
+        let names = vec![
+            "Rust".to_string(),
+            "JetBrains".to_string(),
+            "rust".to_string(),
+            "C".to_string(),
+            "C++".to_string(),
+        ];
+
+        let gitignore = source.merge(&names).unwrap();
+        assert!(gitignore.starts_with("### Rust ###\n"));
+        assert!(gitignore.contains("\n### JetBrains ###\n"));
+        assert_eq!(gitignore.matches("### Rust ###").count(), 1);
+        assert_eq!(gitignore.lines().filter(|line| *line == "*.o").count(), 1);
+
+        let error = source.merge(&["Rust".to_string(), "Unknown".to_string()]);
+        assert!(error.unwrap_err().to_string().contains("Unknown"));
+
+        std::fs::remove_dir_all(&source.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_encode_name() {
+        assert_eq!(GitignoreSource::encode_name("C++"), "C%2B%2B");
+        assert_eq!(
+            GitignoreSource::encode_name("VisualStudioCode"),
+            "VisualStudioCode"
+        );
+    }
+
+    #[test]
+    fn test_reject_invalid_names() {
+        let source = source("scaffer_test_gitignore_empty");
+        let output = Output::new(OutputFormat::Json);
+
+        for name in ["../../x", "Global/Rust", "..\\x", "..", ""] {
+            let error = source.load(name).unwrap_err();
+            assert!(error
+                .to_string()
+                .contains("Invalid gitignore template name"));
+            let error = source.fetch(name, &output).unwrap_err();
+            assert!(error
+                .to_string()
+                .contains("Invalid gitignore template name"));
+        }
+        assert!(GitignoreSource::validate_name("C++").is_ok());
+
+        std::fs::remove_dir_all(&source.cache_dir).unwrap();
+    }
+
+    #[test]
+    fn test_local_and_cached_gitignores_are_preferred() {
+        let mut source = source("scaffer_test_gitignore_cache");
+        let local_dir = create_temp_dir("scaffer_test_gitignore_local").unwrap();
+        std::fs::write(source.cache_dir.join("Rust.gitignore"), "/cached\n").unwrap();
+        std::fs::write(source.cache_dir.join("Elixir.gitignore"), "/_build\n").unwrap();
+        std::fs::write(local_dir.join("rust.gitignore"), "/local\n").unwrap();
+
+        assert_eq!(source.load("Rust").unwrap(), "/cached\n");
+        assert!(source.available().contains(&"Elixir".to_string()));
+
+        source.local_dir = Some(local_dir.clone());
+        assert_eq!(source.load("Rust").unwrap(), "/local\n");
+
+        std::fs::remove_dir_all(&source.cache_dir).unwrap();
+        std::fs::remove_dir_all(&local_dir).unwrap();
+    }
+}
@@ -0,0 +1,281 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct Hooks {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pre_generate: Vec<Hook>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_generate: Vec<Hook>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Hook {
+    pub command: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub optional: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// The outcome of a hook that has been run
+#[derive(Debug, Clone, Serialize)]
+pub struct HookReport {
+    pub command: String,
+    pub optional: bool,
+    pub error: Option<String>,
+}
+
+impl Hook {
+    /// Run the hook in the given project directory
+    ///
+    /// The command is run by the system shell and its output is streamed to the terminal.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_path` - The path of the project, the working directory is relative to it
+    /// * `variables` - Additional environment variables for the command
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Box<dyn std::error::Error>>` - The result of the hook
+    ///
+    /// # Errors
+    ///
+    /// * If the command cannot be started
+    /// * If the command exits with a non-zero status
+    pub fn run(
+        &self,
+        project_path: &Path,
+        variables: &HashMap<String, String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let status = self.command(project_path, variables).status()?;
+
+        if !status.success() {
+            return Err(self
+                .message
+                .clone()
+                .unwrap_or_else(|| format!("`{}` failed with {}", self.command, status))
+                .into());
+        }
+
+        Ok(())
+    }
+
+    /// Run the hook as a check before anything is generated
+    ///
+    /// The output of the command is captured and used as the error message if the check fails,
+    /// unless the hook declares its own message.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_dir` - The directory the working directory is relative to
+    /// * `variables` - Additional environment variables for the command
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), Box<dyn std::error::Error>>` - The result of the check
+    ///
+    /// # Errors
+    ///
+    /// * If the command cannot be started
+    /// * If the command exits with a non-zero status
+    pub fn check(
+        &self,
+        base_dir: &Path,
+        variables: &HashMap<String, String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let output = self.command(base_dir, variables).output()?;
+
+        if !output.status.success() {
+            if let Some(message) = &self.message {
+                return Err(message.clone().into());
+            }
+
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            let message = if stderr.trim().is_empty() {
+                stdout.trim().to_string()
+            } else {
+                stderr.trim().to_string()
+            };
+
+            return Err(if message.is_empty() {
+                format!("`{}` failed with {}", self.command, output.status)
+            } else {
+                message
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Build the shell command of the hook
+    ///
+    /// # Arguments
+    ///
+    /// * `base_dir` - The directory the working directory is relative to
+    /// * `variables` - Additional environment variables for the command
+    ///
+    /// # Returns
+    ///
+    /// The command ready to be run
+    fn command(&self, base_dir: &Path, variables: &HashMap<String, String>) -> Command {
+        let working_dir = match &self.working_dir {
+            Some(dir) => base_dir.join(dir),
+            None => base_dir.to_path_buf(),
+        };
+
+        let mut command = shell_command(&self.command);
+        command
+            .current_dir(working_dir)
+            .envs(variables)
+            .envs(&self.env);
+
+        command
+    }
+
+    /// Run the given hooks one after another
+    ///
+    /// Running stops after the first failing required hook, failing optional hooks are only reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `hooks` - The hooks to run
+    /// * `project_path` - The path of the project
+    /// * `variables` - Additional environment variables for the commands
+    /// * `capture` - Capture the output of the commands instead of streaming it to the terminal
+    ///
+    /// # Returns
+    ///
+    /// The reports of the hooks that have been run
+    pub fn run_all(
+        hooks: &[Hook],
+        project_path: &Path,
+        variables: &HashMap<String, String>,
+        capture: bool,
+    ) -> Vec<HookReport> {
+        let mut reports = vec![];
+
+        for hook in hooks {
+            let result = if capture {
+                hook.check(project_path, variables)
+            } else {
+                hook.run(project_path, variables)
+            };
+            let error = result.err().map(|err| err.to_string());
+            let failed = error.is_some() && !hook.optional;
+
+            reports.push(HookReport {
+                command: hook.command.clone(),
+                optional: hook.optional,
+                error,
+            });
+
+            if failed {
+                break;
+            }
+        }
+
+        reports
+    }
+}
+
+/// Build a command that runs the given command line with the system shell
+///
+/// # Arguments
+///
+/// * `command` - The command line to run
+///
+/// # Returns
+///
+/// The command ready to be configured and run
+pub(crate) fn shell_command(command: &str) -> Command {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+    shell.arg(command);
+
+    shell
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_temp_dir;
+
+    fn hook(command: &str, optional: bool) -> Hook {
+        Hook {
+            command: command.to_string(),
+            working_dir: None,
+            env: BTreeMap::new(),
+            optional,
+            message: None,
+        }
+    }
+
+    #[test]
+    fn test_run_hook_with_env() {
+        let dir = create_temp_dir("scaffer_test_run_hook").unwrap();
+
+        let mut hook = hook("echo $GREETING $SCAFFER_PROJECT_NAME > out.txt", false);
+        hook.env.insert("GREETING".to_string(), "hello".to_string());
+        let variables = HashMap::from([("SCAFFER_PROJECT_NAME".to_string(), "demo".to_string())]);
+
+        hook.run(&dir, &variables).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.join("out.txt")).unwrap().trim(),
+            "hello demo"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_check_hook_reports_message() {
+        let dir = std::env::temp_dir();
+
+        let failing = hook("echo 'name is reserved' >&2; exit 1", false);
+        let error = failing.check(&dir, &HashMap::new()).unwrap_err();
+        assert_eq!(error.to_string(), "name is reserved");
+
+        let mut custom = hook("exit 1", false);
+        custom.message = Some("cargo is not installed".to_string());
+        let error = custom.check(&dir, &HashMap::new()).unwrap_err();
+        assert_eq!(error.to_string(), "cargo is not installed");
+
+        assert!(hook("true", false).check(&dir, &HashMap::new()).is_ok());
+    }
+
+    #[test]
+    fn test_run_all_stops_after_required_failure() {
+        let dir = std::env::temp_dir();
+        let hooks = vec![
+            hook("exit 1", true),
+            hook("exit 2", false),
+            hook("true", false),
+        ];
+
+        let reports = Hook::run_all(&hooks, &dir, &HashMap::new(), false);
+
+        assert_eq!(reports.len(), 2);
+        assert!(reports[0].error.is_some());
+        assert!(reports[1].error.is_some());
+    }
+}
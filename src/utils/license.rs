@@ -0,0 +1,181 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The license texts that are bundled into the binary, by SPDX identifier
+const BUNDLED_LICENSES: &[(&str, &str)] = &[
+    ("MIT", include_str!("../../licenses/MIT.txt")),
+    ("Apache-2.0", include_str!("../../licenses/Apache-2.0.txt")),
+    ("GPL-3.0-only", include_str!("../../licenses/GPL-3.0.txt")),
+    (
+        "GPL-3.0-or-later",
+        include_str!("../../licenses/GPL-3.0.txt"),
+    ),
+    ("LGPL-3.0-only", include_str!("../../licenses/LGPL-3.0.txt")),
+    ("MPL-2.0", include_str!("../../licenses/MPL-2.0.txt")),
+    (
+        "BSD-2-Clause",
+        include_str!("../../licenses/BSD-2-Clause.txt"),
+    ),
+    (
+        "BSD-3-Clause",
+        include_str!("../../licenses/BSD-3-Clause.txt"),
+    ),
+    ("ISC", include_str!("../../licenses/ISC.txt")),
+    ("Unlicense", include_str!("../../licenses/Unlicense.txt")),
+];
+
+/// List the SPDX identifiers of the bundled licenses
+///
+/// # Returns
+///
+/// The SPDX identifiers, the most common ones first
+pub fn license_ids() -> Vec<String> {
+    BUNDLED_LICENSES
+        .iter()
+        .map(|(id, _)| id.to_string())
+        .collect()
+}
+
+/// Find the canonical SPDX identifier of a bundled license, ignoring the case
+///
+/// # Arguments
+///
+/// * `id` - The SPDX identifier of the license (e.g. mit, Apache-2.0)
+///
+/// # Returns
+///
+/// * `Result<String, Box<dyn std::error::Error>>` - The canonical SPDX identifier
+///
+/// # Errors
+///
+/// * If there is no bundled license with the given identifier
+pub fn find_license(id: &str) -> Result<String, Box<dyn std::error::Error>> {
+    BUNDLED_LICENSES
+        .iter()
+        .find(|(bundled, _)| bundled.eq_ignore_ascii_case(id))
+        .map(|(bundled, _)| bundled.to_string())
+        .ok_or_else(|| {
+            format!(
+                "Unknown license {}, available licenses: {}",
+                id,
+                license_ids().join(", ")
+            )
+            .into()
+        })
+}
+
+/// Check if the text of a bundled license names the copyright holder
+///
+/// # Arguments
+///
+/// * `id` - The SPDX identifier of the license
+///
+/// # Returns
+///
+/// * `Result<bool, Box<dyn std::error::Error>>` - Whether the license text has an author
+///
+/// # Errors
+///
+/// * If there is no bundled license with the given identifier
+pub fn license_uses_author(id: &str) -> Result<bool, Box<dyn std::error::Error>> {
+    let id = find_license(id)?;
+
+    Ok(BUNDLED_LICENSES
+        .iter()
+        .any(|(bundled, text)| *bundled == id && text.contains("{{author}}")))
+}
+
+/// Render the full text of a bundled license with the author and year filled in
+///
+/// # Arguments
+///
+/// * `id` - The SPDX identifier of the license
+/// * `author` - The copyright holder
+/// * `year` - The copyright year
+///
+/// # Returns
+///
+/// * `Result<String, Box<dyn std::error::Error>>` - The license text
+///
+/// # Errors
+///
+/// * If there is no bundled license with the given identifier
+pub fn render_license(
+    id: &str,
+    author: &str,
+    year: i64,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let id = find_license(id)?;
+    let text = BUNDLED_LICENSES
+        .iter()
+        .find(|(bundled, _)| *bundled == id)
+        .map(|(_, text)| *text)
+        .unwrap_or_default();
+
+    Ok(text
+        .trim_start_matches('\n')
+        .replace("{{author}}", author)
+        .replace("{{year}}", &year.to_string()))
+}
+
+/// The current year in UTC
+///
+/// # Returns
+///
+/// The current year
+pub fn current_year() -> i64 {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default();
+
+    year_from_days(seconds.div_euclid(86_400))
+}
+
+/// Convert the days since the unix epoch into the civil year
+///
+/// # Arguments
+///
+/// * `days` - The days since 1970-01-01
+///
+/// # Returns
+///
+/// The year of the given day
+fn year_from_days(days: i64) -> i64 {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+
+    year_of_era + era * 400 + if month >= 10 { 1 } else { 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_license() {
+        let license = render_license("mit", "Jane Doe", 2025).unwrap();
+
+        assert!(license.starts_with("MIT License"));
+        assert!(license.contains("Copyright (c) 2025 Jane Doe"));
+        assert!(render_license("Unknown-1.0", "Jane Doe", 2025).is_err());
+        assert_eq!(find_license("apache-2.0").unwrap(), "Apache-2.0");
+
+        assert!(license_uses_author("MIT").unwrap());
+        assert!(!license_uses_author("Apache-2.0").unwrap());
+        assert!(!license_uses_author("unlicense").unwrap());
+    }
+
+    #[test]
+    fn test_year_from_days() {
+        assert_eq!(year_from_days(0), 1970);
+        assert_eq!(year_from_days(364), 1970);
+        assert_eq!(year_from_days(365), 1971);
+        assert_eq!(year_from_days(20_088), 2024);
+        assert_eq!(year_from_days(20_089), 2025);
+    }
+}
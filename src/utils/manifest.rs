@@ -0,0 +1,334 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{render_license, GitignoreSource, Template, TASK_FILE};
+
+/// The file name of the manifest in the root of a generated project
+pub const MANIFEST_FILE: &str = ".scaffer.json";
+
+/// The record of how a project was generated
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Manifest {
+    pub scaffer_version: String,
+    pub template: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_version: Option<String>,
+    pub template_hash: String,
+    pub answers: BTreeMap<String, String>,
+    pub files: BTreeMap<PathBuf, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_snapshot: Option<Template>,
+    /// Files an update left with conflict markers, they keep their previous checksum until resolved
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub conflicts: BTreeSet<PathBuf>,
+}
+
+impl Manifest {
+    /// Create the manifest of a generated project
+    ///
+    /// The checksums are calculated for every file in the project directory, except the
+    /// manifest itself and the `.git` directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `template_id` - The id of the template (e.g. rust)
+    /// * `template` - The template of the project
+    /// * `answers` - The answers and variables used to generate the project
+    /// * `project_path` - The directory the project was generated into
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, Box<dyn std::error::Error>>` - The manifest
+    ///
+    /// # Errors
+    ///
+    /// * If the template cannot be serialized
+    /// * If the generated files cannot be read
+    pub fn new(
+        template_id: &str,
+        template: &Template,
+        answers: BTreeMap<String, String>,
+        project_path: &Path,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut files = BTreeMap::new();
+        Self::collect_checksums(project_path, project_path, &mut files)?;
+
+        Ok(Self {
+            scaffer_version: env!("CARGO_PKG_VERSION").to_string(),
+            template: template_id.to_string(),
+            template_version: template.version.clone(),
+            template_hash: Self::template_hash(template)?,
+            answers,
+            files,
+            template_snapshot: Some(template.clone()),
+            conflicts: BTreeSet::new(),
+        })
+    }
+
+    /// Load the manifest from the root of a generated project
+    ///
+    /// # Arguments
+    ///
+    /// * `project_path` - The root directory of the project
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, Box<dyn std::error::Error>>` - The manifest
+    ///
+    /// # Errors
+    ///
+    /// * If the project has no manifest or the manifest is invalid
+    pub fn load(project_path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = project_path.join(MANIFEST_FILE);
+        let manifest = std::fs::read_to_string(&path)
+            .map_err(|err| format!("Could not read {}: {}", path.display(), err))?;
+
+        Ok(serde_json::from_str(&manifest)?)
+    }
+
+    /// Save the manifest into the root of a project
+    ///
+    /// # Arguments
+    ///
+    /// * `project_path` - The root directory of the project
+    ///
+    /// # Errors
+    ///
+    /// * If the manifest cannot be written
+    pub fn save(&self, project_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        let manifest = serde_json::to_string_pretty(self)?;
+        std::fs::write(project_path.join(MANIFEST_FILE), manifest + "\n")?;
+
+        Ok(())
+    }
+
+    /// Render the files of the project from the given template and the recorded answers
+    ///
+    /// The files are rendered the same way `scaffer create` writes them: the template files,
+    /// the generated README, the LICENSE if a license was chosen and the .gitignore file if
+    /// the project was created with one, normalized with the formatting of the template.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template to render
+    /// * `project_path` - The root directory of the project
+    /// * `gitignores` - The source of the gitignore templates
+    ///
+    /// # Returns
+    ///
+    /// * `Result<BTreeMap<PathBuf, String>, Box<dyn std::error::Error>>` - The rendered content by relative path
+    ///
+    /// # Errors
+    ///
+    /// * If a file, the license or the .gitignore file cannot be rendered
+    pub fn render_project(
+        &self,
+        template: &Template,
+        project_path: &Path,
+        gitignores: &GitignoreSource,
+    ) -> Result<BTreeMap<PathBuf, String>, Box<dyn std::error::Error>> {
+        let answer = |name: &str| self.answers.get(name).cloned().unwrap_or_default();
+        let mut variables: HashMap<String, String> = self.answers.clone().into_iter().collect();
+        variables.insert(
+            "project_path".to_string(),
+            project_path.display().to_string(),
+        );
+
+        let mut files = BTreeMap::new();
+        for file in &template.structure.files {
+            files.insert(
+                file.path.clone(),
+                template.render(file.content.clone(), &variables)?,
+            );
+        }
+
+        if !template.has_readme() {
+            files.insert(
+                PathBuf::from("README.md"),
+                template.render_readme(&variables)?,
+            );
+        }
+
+        if let Some(tasks) = template.render_tasks(&variables)? {
+            files.insert(PathBuf::from(TASK_FILE), tasks);
+        }
+
+        let license = answer("license");
+        if !license.is_empty() {
+            let year = answer("year").parse().unwrap_or_default();
+            files.insert(
+                PathBuf::from("LICENSE"),
+                render_license(&license, &answer("author"), year)?,
+            );
+        }
+
+        if self.files.contains_key(Path::new(".gitignore")) {
+            let extra: Vec<String> = answer("gitignore")
+                .split(',')
+                .filter(|name| !name.is_empty())
+                .map(String::from)
+                .collect();
+            files.insert(
+                PathBuf::from(".gitignore"),
+                gitignores.merge(&template.gitignore_names(&answer("language"), &extra))?,
+            );
+        }
+
+        let editorconfig = template.editorconfig(project_path, &variables)?;
+        Ok(files
+            .into_iter()
+            .map(|(path, content)| {
+                let content = template.format_file(&path, &content, project_path, &editorconfig);
+                (path, content)
+            })
+            .collect())
+    }
+
+    /// Calculate the SHA-256 checksum of the given content
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The content to hash
+    ///
+    /// # Returns
+    ///
+    /// The checksum as lowercase hex string
+    pub fn checksum(content: &[u8]) -> String {
+        Sha256::digest(content)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    /// Calculate the checksum of a template
+    ///
+    /// The template is hashed in a canonical form with sorted keys, so the same template
+    /// always has the same checksum.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template to hash
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, Box<dyn std::error::Error>>` - The checksum as lowercase hex string
+    ///
+    /// # Errors
+    ///
+    /// * If the template cannot be serialized
+    pub fn template_hash(template: &Template) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(Self::checksum(
+            serde_json::to_value(template)?.to_string().as_bytes(),
+        ))
+    }
+
+    /// Collect the checksums of all files in a directory recursively
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root directory of the project
+    /// * `dir` - The directory to collect the checksums from
+    /// * `files` - The collected checksums by path relative to the root
+    ///
+    /// # Errors
+    ///
+    /// * If a directory or file cannot be read
+    fn collect_checksums(
+        root: &Path,
+        dir: &Path,
+        files: &mut BTreeMap<PathBuf, String>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let relative = path.strip_prefix(root)?.to_path_buf();
+
+            if relative == Path::new(".git") || relative == Path::new(MANIFEST_FILE) {
+                continue;
+            }
+
+            if entry.file_type()?.is_dir() {
+                Self::collect_checksums(root, &path, files)?;
+            } else {
+                files.insert(relative, Self::checksum(&std::fs::read(&path)?));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{create_temp_dir, Config};
+
+    #[test]
+    fn test_manifest_roundtrip() {
+        let dir = create_temp_dir("scaffer_test_manifest").unwrap();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::create_dir_all(dir.join(".git")).unwrap();
+        std::fs::write(dir.join("src/main.rs"), "fn main() {}").unwrap();
+        std::fs::write(dir.join(".git/HEAD"), "ref: refs/heads/main").unwrap();
+
+        let template = Template::load_template(
+            "rust".to_string(),
+            &Config::new(Config::bundled_template_dir()),
+        )
+        .unwrap();
+        let answers = BTreeMap::from([("project_name".to_string(), "demo".to_string())]);
+        let manifest = Manifest::new("rust", &template, answers, &dir).unwrap();
+        manifest.save(&dir).unwrap();
+
+        let loaded = Manifest::load(&dir).unwrap();
+        assert_eq!(loaded.template, "rust");
+        assert_eq!(loaded.answers["project_name"], "demo");
+        assert_eq!(loaded.files.len(), 1);
+        assert_eq!(
+            loaded.files[Path::new("src/main.rs")],
+            Manifest::checksum(b"fn main() {}")
+        );
+        assert_eq!(loaded.template_hash, manifest.template_hash);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_checksum() {
+        assert_eq!(
+            Manifest::checksum(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn test_template_hash() {
+        let template = |env: &str| -> Template {
+            serde_json::from_str(&format!(
+                r#"{{
+                    "structure": {{ "directories": [], "files": [] }},
+                    "start_command": "cargo run",
+                    "hooks": {{ "post_generate": [{{ "command": "make", "env": {} }}] }}
+                }}"#,
+                env
+            ))
+            .unwrap()
+        };
+        let env = r#"{ "A": "1", "B": "2", "C": "3", "D": "4", "E": "5" }"#;
+
+        let hash = Manifest::template_hash(&template(env)).unwrap();
+        assert_eq!(Manifest::template_hash(&template(env)).unwrap(), hash);
+        assert_eq!(
+            Manifest::template_hash(&template(
+                r#"{ "E": "5", "D": "4", "C": "3", "B": "2", "A": "1" }"#
+            ))
+            .unwrap(),
+            hash
+        );
+    }
+}
@@ -1,5 +1,39 @@
 mod config;
+mod formatting;
+mod git;
+mod gitignore;
+mod hooks;
+mod license;
+mod manifest;
+mod naming;
+mod output;
+mod parent_build;
+mod prompt;
+mod staging;
+mod tasks;
+mod template_source;
 mod templates;
+mod theme;
 
-pub use config::Config;
-pub use templates::{File, Structure, Template};
+pub use config::{Config, GitConfig, GitignoreConfig};
+pub use formatting::{has_significant_indentation, EditorConfig, Formatting};
+pub use git::{clone_repository, configured_user_name, init_repository};
+pub use gitignore::GitignoreSource;
+pub(crate) use hooks::shell_command;
+pub use hooks::{Hook, HookReport, Hooks};
+pub use license::{current_year, find_license, license_ids, license_uses_author, render_license};
+pub use manifest::{Manifest, MANIFEST_FILE};
+pub use naming::{suggest_name, validate_identifier, validate_name};
+pub use output::{
+    MessageKind, Output, OutputFormat, PlainReporter, Reporter, SilentReporter, TextReporter,
+    Verbosity,
+};
+pub use parent_build::ParentBuild;
+pub use prompt::Prompter;
+pub use staging::Staging;
+pub use tasks::{TaskFailed, TaskFile, TaskRun, TASK_FILE};
+#[cfg(test)]
+pub(crate) use template_source::create_temp_dir;
+pub use template_source::TemplateSource;
+pub use templates::{File, GeneratedFile, Member, Structure, Template};
+pub use theme::{Style, Theme};
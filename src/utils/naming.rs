@@ -0,0 +1,424 @@
+/// Characters that are not allowed in file names on at least one supported platform
+const UNSAFE_CHARACTERS: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// File names that are reserved on Windows
+const WINDOWS_RESERVED: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// Names cargo refuses as crate names: keywords and the names of the built-in crates
+const RUST_RESERVED: &[&str] = &[
+    "abstract",
+    "alloc",
+    "as",
+    "async",
+    "await",
+    "become",
+    "box",
+    "break",
+    "const",
+    "continue",
+    "core",
+    "crate",
+    "do",
+    "dyn",
+    "else",
+    "enum",
+    "extern",
+    "false",
+    "final",
+    "fn",
+    "for",
+    "if",
+    "impl",
+    "in",
+    "let",
+    "loop",
+    "macro",
+    "match",
+    "mod",
+    "move",
+    "mut",
+    "override",
+    "priv",
+    "proc_macro",
+    "pub",
+    "ref",
+    "return",
+    "self",
+    "std",
+    "static",
+    "struct",
+    "super",
+    "test",
+    "trait",
+    "true",
+    "try",
+    "type",
+    "typeof",
+    "unsafe",
+    "unsized",
+    "use",
+    "virtual",
+    "where",
+    "while",
+    "yield",
+];
+
+const PYTHON_RESERVED: &[&str] = &[
+    "false", "none", "true", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+const GO_RESERVED: &[&str] = &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+];
+
+const JAVA_RESERVED: &[&str] = &[
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "true",
+    "try",
+    "void",
+    "volatile",
+    "while",
+    "_",
+];
+
+/// Validate the project name against the naming rules of the language
+///
+/// Every language checks for empty names, filesystem-unsafe characters and names reserved
+/// by the operating system. Rust, Python, Go and Java additionally check the rules of their
+/// package or identifier names and their reserved words.
+///
+/// # Arguments
+///
+/// * `language` - The programming language of the project
+/// * `name` - The name of the project
+///
+/// # Returns
+///
+/// * `Result<(), String>` - The reason why the name is invalid
+pub fn validate_name(language: &str, name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("The name must not be empty".to_string());
+    }
+    if name != name.trim() {
+        return Err("The name must not start or end with whitespace".to_string());
+    }
+    if name == "." || name == ".." {
+        return Err("The name must not be `.` or `..`".to_string());
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|c| UNSAFE_CHARACTERS.contains(c) || c.is_control())
+    {
+        return Err(format!(
+            "The name must not contain `{}`",
+            c.escape_default()
+        ));
+    }
+    if WINDOWS_RESERVED.contains(&name.to_lowercase().as_str()) {
+        return Err(format!("`{}` is a reserved file name on Windows", name));
+    }
+
+    match language.to_lowercase().as_str() {
+        "rust" => {
+            if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                return Err("Rust crate names must start with a letter".to_string());
+            }
+            if !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                return Err(
+                    "Rust crate names may only contain letters, digits, `-` and `_`".to_string(),
+                );
+            }
+            if RUST_RESERVED.contains(&name.replace('-', "_").as_str()) {
+                return Err(format!("`{}` is a reserved name in Rust", name));
+            }
+        }
+        "python" => {
+            if !name.starts_with(|c: char| c.is_ascii_alphanumeric())
+                || !name.ends_with(|c: char| c.is_ascii_alphanumeric())
+            {
+                return Err(
+                    "Python package names must start and end with a letter or digit".to_string(),
+                );
+            }
+            if !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+            {
+                return Err(
+                    "Python package names may only contain letters, digits, `-`, `_` and `.`"
+                        .to_string(),
+                );
+            }
+            if PYTHON_RESERVED.contains(&name.to_lowercase().as_str()) {
+                return Err(format!("`{}` is a reserved word in Python", name));
+            }
+        }
+        "go" => {
+            if name.starts_with(['.', '-']) {
+                return Err("Go module names must not start with `.` or `-`".to_string());
+            }
+            if !name.chars().all(|c| {
+                c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_' | '.' | '~')
+            }) {
+                return Err(
+                    "Go module names may only contain lowercase letters, digits, `-`, `_`, `.` and `~`"
+                        .to_string(),
+                );
+            }
+            if GO_RESERVED.contains(&name) {
+                return Err(format!("`{}` is a reserved word in Go", name));
+            }
+        }
+        "java" => {
+            if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$') {
+                return Err("Java identifiers must start with a letter, `_` or `$`".to_string());
+            }
+            if !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+            {
+                return Err(
+                    "Java identifiers may only contain letters, digits, `_` and `$`".to_string(),
+                );
+            }
+            if JAVA_RESERVED.contains(&name) {
+                return Err(format!("`{}` is a reserved word in Java", name));
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Validate the name of a component added by a generator of the template
+///
+/// Component names end up as module and package names in the code, so for Rust, Python, Go
+/// and Java they have to be identifiers that are no reserved words. Other languages (e.g. the
+/// pages of an HTML project) only need a safe file name.
+///
+/// # Arguments
+///
+/// * `language` - The programming language of the project
+/// * `name` - The name of the component
+///
+/// # Returns
+///
+/// * `Result<(), String>` - The reason why the name is invalid
+pub fn validate_identifier(language: &str, name: &str) -> Result<(), String> {
+    validate_name("", name)?;
+
+    let (language, reserved) = match language.to_lowercase().as_str() {
+        "rust" => ("Rust", RUST_RESERVED),
+        "python" => ("Python", PYTHON_RESERVED),
+        "go" => ("Go", GO_RESERVED),
+        "java" => ("Java", JAVA_RESERVED),
+        _ => return Ok(()),
+    };
+
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Err(format!(
+            "{} identifiers must start with a letter or `_` and may only contain letters, digits and `_`",
+            language
+        ));
+    }
+    let keyword = if language == "Python" {
+        name.to_lowercase()
+    } else {
+        name.to_string()
+    };
+    if reserved.contains(&keyword.as_str()) {
+        return Err(format!("`{}` is a reserved name in {}", name, language));
+    }
+
+    Ok(())
+}
+
+/// Derive a valid project name for the language from the given name
+///
+/// # Arguments
+///
+/// * `language` - The programming language of the project
+/// * `name` - The name to derive the slug from
+///
+/// # Returns
+///
+/// * `String` - The suggested project name
+pub fn suggest_name(language: &str, name: &str) -> String {
+    let words: Vec<String> = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_string())
+        .collect();
+
+    let mut slug = match language.to_lowercase().as_str() {
+        "java" => words
+            .iter()
+            .enumerate()
+            .map(|(index, word)| {
+                let word = word.to_lowercase();
+                if index == 0 {
+                    word
+                } else {
+                    let mut chars = word.chars();
+                    chars
+                        .next()
+                        .map(|first| first.to_uppercase().collect::<String>() + chars.as_str())
+                        .unwrap_or_default()
+                }
+            })
+            .collect::<String>(),
+        _ => words
+            .iter()
+            .map(|word| word.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("-"),
+    };
+
+    if slug.is_empty() {
+        slug = "project".to_string();
+    }
+    if !slug.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        slug = format!("project-{}", slug);
+        if language.eq_ignore_ascii_case("java") {
+            slug = slug.replace('-', "");
+        }
+    }
+    if validate_name(language, &slug).is_err() {
+        slug = if language.eq_ignore_ascii_case("java") {
+            format!("{}Project", slug)
+        } else {
+            format!("{}-project", slug)
+        };
+    }
+
+    slug
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert!(validate_name("Rust", "my-app").is_ok());
+        assert!(validate_name("Rust", "my app").is_err());
+        assert!(validate_name("Rust", "1app").is_err());
+        assert!(validate_name("Rust", "test").is_err());
+        assert!(validate_name("Python", "my_package.core").is_ok());
+        assert!(validate_name("Python", "import").is_err());
+        assert!(validate_name("Go", "MyModule").is_err());
+        assert!(validate_name("Java", "MyApp").is_ok());
+        assert!(validate_name("Java", "my-app").is_err());
+        assert!(validate_name("Java", "class").is_err());
+        assert!(validate_name("HTML", "My Site").is_ok());
+        assert!(validate_name("HTML", "a/b").is_err());
+        assert!(validate_name("C", "CON").is_err());
+    }
+
+    #[test]
+    fn test_validate_identifier() {
+        assert!(validate_identifier("Rust", "parser_utils").is_ok());
+        assert!(validate_identifier("rust", "my-mod").is_err());
+        assert!(validate_identifier("Rust", "fn").is_err());
+        assert!(validate_identifier("Rust", "2d").is_err());
+        assert!(validate_identifier("Python", "class").is_err());
+        assert!(validate_identifier("Python", "my.module").is_err());
+        assert!(validate_identifier("Go", "func").is_err());
+        assert!(validate_identifier("HTML", "about-us").is_ok());
+        assert!(validate_identifier("HTML", "a/b").is_err());
+    }
+
+    #[test]
+    fn test_suggest_name() {
+        assert_eq!(suggest_name("Rust", "My App"), "my-app");
+        assert_eq!(suggest_name("Rust", "test"), "test-project");
+        assert_eq!(suggest_name("Rust", "1st app"), "project-1st-app");
+        assert_eq!(suggest_name("Java", "my cool-app"), "myCoolApp");
+        assert_eq!(suggest_name("Java", "class"), "classProject");
+        assert_eq!(suggest_name("Go", "Web Server"), "web-server");
+        assert_eq!(suggest_name("Python", "!!!"), "project");
+    }
+}
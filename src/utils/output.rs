@@ -0,0 +1,647 @@
+use std::{cell::RefCell, fmt::Display, rc::Rc};
+
+use clap::ValueEnum;
+use colored::*;
+use serde_json::{Map, Value};
+
+use super::{Style, Theme};
+
+/// The format of the command output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable progress with colors and symbols
+    #[default]
+    Text,
+    /// Human readable progress without colors and symbols, e.g. for log files
+    Plain,
+    /// A single JSON document with the result of the command, without any decorations
+    Json,
+}
+
+/// How much progress is reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Only the results of the commands, warnings and failures
+    Quiet,
+    /// The progress of the commands
+    #[default]
+    Normal,
+    /// The progress including every file written and every network request
+    Verbose,
+}
+
+/// The kind of a reported message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    /// The result of a command (e.g. the listed templates), also shown in quiet mode
+    Print,
+    /// A line of progress
+    Line,
+    /// The start of a step (e.g. creating the project files)
+    Step,
+    /// A successful part of a step
+    Success,
+    /// A part of a step that did not need any changes
+    Unchanged,
+    /// Something that was skipped
+    Skipped,
+    /// A warning
+    Warning,
+    /// A failed part of a step that does not abort the command
+    Failure,
+    /// A detail only shown in verbose mode (e.g. a written file)
+    Detail,
+    /// The heading of a section of the summary (e.g. Next steps)
+    Heading,
+    /// An item of a section of the summary
+    Item,
+    /// The final message of a successful command
+    Done,
+}
+
+/// Presents the messages of the commands to the user
+///
+/// Implement this trait to embed the commands into another application, e.g. to show the
+/// progress in a GUI or to write it to a log.
+pub trait Reporter: std::fmt::Debug {
+    /// Report a message of a command
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the message
+    /// * `message` - The message, may contain ANSI colors
+    fn report(&self, kind: MessageKind, message: &str);
+}
+
+/// Prints the messages with the colors and symbols of a theme
+#[derive(Debug, Clone, Default)]
+pub struct TextReporter {
+    theme: Theme,
+}
+
+impl TextReporter {
+    /// Create a new text reporter
+    ///
+    /// # Arguments
+    ///
+    /// * `theme` - The theme of the messages
+    ///
+    /// # Returns
+    ///
+    /// The text reporter
+    pub fn new(theme: Theme) -> Self {
+        Self { theme }
+    }
+
+    /// Format a message of a step with the branch and the symbol of its kind
+    ///
+    /// Empty symbols of the theme are left out.
+    ///
+    /// # Arguments
+    ///
+    /// * `indent` - The indentation of the line
+    /// * `branch` - Whether the line starts with the branch symbol
+    /// * `symbol` - The symbol of the kind and its style
+    /// * `message` - The message
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The formatted line
+    fn format(&self, indent: &str, branch: bool, symbol: (&str, Style), message: &str) -> String {
+        let branch = match branch {
+            true => self.theme.symbols.branch.as_str(),
+            false => "",
+        };
+        let symbol = self.theme.paint(symbol.0, symbol.1).to_string();
+        let parts: Vec<&str> = [branch, symbol.as_str(), message]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect();
+
+        format!("{}{}", indent, parts.join(" "))
+    }
+}
+
+impl Reporter for TextReporter {
+    fn report(&self, kind: MessageKind, message: &str) {
+        let theme = &self.theme;
+        let symbols = &theme.symbols;
+        let line = match kind {
+            MessageKind::Print | MessageKind::Line => message.to_string(),
+            MessageKind::Step => self.format("", true, (&symbols.step, Style::Accent), message),
+            MessageKind::Success => self.format(
+                "   ",
+                true,
+                (&symbols.success, Style::Success),
+                &theme.paint(message, Style::Success).to_string(),
+            ),
+            MessageKind::Unchanged => {
+                self.format("   ", true, (&symbols.unchanged, Style::Warning), message)
+            }
+            MessageKind::Skipped => self.format(
+                "   ",
+                true,
+                (&symbols.skipped, Style::Warning),
+                &theme.paint(message, Style::Warning).to_string(),
+            ),
+            MessageKind::Warning => self.format(
+                "   ",
+                true,
+                (&symbols.warning, Style::Warning),
+                &theme.paint(message, Style::Warning).to_string(),
+            ),
+            MessageKind::Failure => self.format(
+                "   ",
+                true,
+                (&symbols.failure, Style::Error),
+                &theme.paint(message, Style::Error).to_string(),
+            ),
+            MessageKind::Detail => format!("      {}", theme.paint(message, Style::Muted)),
+            MessageKind::Heading => format!("\n{}", theme.paint(message, Style::Heading)),
+            MessageKind::Item => self.format("   ", false, (&symbols.item, Style::Accent), message),
+            MessageKind::Done => {
+                let message = match symbols.done.is_empty() {
+                    true => message.to_string(),
+                    false => format!("{} {}", message, symbols.done),
+                };
+                let message = theme.paint(&message, Style::Success);
+                match theme.bold && theme.colors {
+                    true => format!("\n{}", message.bold()),
+                    false => format!("\n{}", message),
+                }
+            }
+        };
+
+        println!("{}", line);
+    }
+}
+
+/// Prints the messages without colors and symbols
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlainReporter;
+
+impl Reporter for PlainReporter {
+    fn report(&self, kind: MessageKind, message: &str) {
+        let message = strip_colors(message);
+        match kind {
+            MessageKind::Print | MessageKind::Line | MessageKind::Step => println!("{}", message),
+            MessageKind::Success | MessageKind::Detail | MessageKind::Item => {
+                println!("  {}", message)
+            }
+            MessageKind::Unchanged => println!("  unchanged: {}", message),
+            MessageKind::Skipped => println!("  skipped: {}", message),
+            MessageKind::Warning => println!("  warning: {}", message),
+            MessageKind::Failure => println!("  error: {}", message),
+            MessageKind::Heading | MessageKind::Done => println!("\n{}", message),
+        }
+    }
+}
+
+/// Drops all messages, e.g. to run the commands silently in another application
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SilentReporter;
+
+impl Reporter for SilentReporter {
+    fn report(&self, _kind: MessageKind, _message: &str) {}
+}
+
+/// Remove the ANSI escape sequences from a message
+///
+/// # Arguments
+///
+/// * `message` - The message
+///
+/// # Returns
+///
+/// * `String` - The message without colors
+fn strip_colors(message: &str) -> String {
+    let mut stripped = String::with_capacity(message.len());
+    let mut chars = message.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            stripped.push(c);
+        }
+    }
+
+    stripped
+}
+
+/// Writes the progress and the result of a command in the selected format
+///
+/// In text and plain mode the progress is passed to the reporter as it happens, filtered by
+/// the verbosity. In JSON mode the progress is suppressed, warnings are collected and only the
+/// final result is printed. Clones share the reporter and the collected warnings.
+#[derive(Debug, Clone)]
+pub struct Output {
+    pub format: OutputFormat,
+    pub verbosity: Verbosity,
+    theme: Rc<Theme>,
+    reporter: Rc<dyn Reporter>,
+    warnings: Rc<RefCell<Vec<String>>>,
+}
+
+impl Default for Output {
+    fn default() -> Self {
+        Self::new(OutputFormat::default())
+    }
+}
+
+impl Output {
+    /// Create a new output in the given format with the reporter of the format
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - The format of the output
+    ///
+    /// # Returns
+    ///
+    /// The output
+    pub fn new(format: OutputFormat) -> Self {
+        let reporter: Rc<dyn Reporter> = match format {
+            OutputFormat::Text => Rc::new(TextReporter::default()),
+            OutputFormat::Plain => Rc::new(PlainReporter),
+            OutputFormat::Json => Rc::new(SilentReporter),
+        };
+
+        Self {
+            format,
+            verbosity: Verbosity::default(),
+            theme: Rc::default(),
+            reporter,
+            warnings: Rc::default(),
+        }
+    }
+
+    /// Set how much progress is reported
+    ///
+    /// # Arguments
+    ///
+    /// * `verbosity` - The verbosity
+    ///
+    /// # Returns
+    ///
+    /// The output with the verbosity
+    pub fn with_verbosity(mut self, verbosity: Verbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Set the theme of the output
+    ///
+    /// In text mode the reporter is replaced by a text reporter with the theme.
+    ///
+    /// # Arguments
+    ///
+    /// * `theme` - The theme
+    ///
+    /// # Returns
+    ///
+    /// The output with the theme
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        if self.format == OutputFormat::Text {
+            self.reporter = Rc::new(TextReporter::new(theme.clone()));
+        }
+        self.theme = Rc::new(theme);
+        self
+    }
+
+    /// The theme of the output
+    ///
+    /// # Returns
+    ///
+    /// * `&Theme` - The theme
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Color a text according to its role in text mode
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to color
+    /// * `style` - The role of the text
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The colored text, unchanged in plain and JSON mode
+    pub fn paint(&self, text: &str, style: Style) -> String {
+        match self.format {
+            OutputFormat::Text => self.theme.paint(text, style).to_string(),
+            _ => text.to_string(),
+        }
+    }
+
+    /// Replace the reporter the messages are passed to
+    ///
+    /// # Arguments
+    ///
+    /// * `reporter` - The reporter
+    ///
+    /// # Returns
+    ///
+    /// The output with the reporter
+    pub fn with_reporter(mut self, reporter: Rc<dyn Reporter>) -> Self {
+        self.reporter = reporter;
+        self
+    }
+
+    /// Check if the output is machine readable
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the output format is JSON
+    pub fn is_json(&self) -> bool {
+        self.format == OutputFormat::Json
+    }
+
+    /// Check if external commands (e.g. hooks) may write to the terminal
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the output of external commands is streamed instead of captured
+    pub fn streams_commands(&self) -> bool {
+        !self.is_json() && self.verbosity != Verbosity::Quiet
+    }
+
+    /// Pass a message to the reporter if the verbosity allows it
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the message
+    /// * `message` - The message
+    fn emit(&self, kind: MessageKind, message: &str) {
+        let shown = match self.verbosity {
+            Verbosity::Quiet => matches!(
+                kind,
+                MessageKind::Print
+                    | MessageKind::Skipped
+                    | MessageKind::Warning
+                    | MessageKind::Failure
+            ),
+            Verbosity::Normal => kind != MessageKind::Detail,
+            Verbosity::Verbose => true,
+        };
+
+        if shown && !self.is_json() {
+            self.reporter.report(kind, message);
+        }
+    }
+
+    /// Print the result of a command as human readable text, also in quiet mode
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The line to print
+    pub fn print(&self, line: impl Display) {
+        self.emit(MessageKind::Print, &line.to_string());
+    }
+
+    /// Print a line of human readable progress
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The line to print
+    pub fn line(&self, line: impl Display) {
+        self.emit(MessageKind::Line, &line.to_string());
+    }
+
+    /// Print the start of a step (e.g. creating the project files)
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The description of the step
+    pub fn step(&self, message: &str) {
+        self.emit(MessageKind::Step, message);
+    }
+
+    /// Print a successful part of a step
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - What has been done
+    pub fn success(&self, message: &str) {
+        self.emit(MessageKind::Success, message);
+    }
+
+    /// Print a part of a step that did not need any changes
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - What has been left unchanged
+    pub fn unchanged(&self, message: &str) {
+        self.emit(MessageKind::Unchanged, message);
+    }
+
+    /// Print and record something that was skipped
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - What has been skipped
+    pub fn skipped(&self, message: &str) {
+        self.emit(MessageKind::Skipped, message);
+        self.warnings.borrow_mut().push(message.to_string());
+    }
+
+    /// Print and record a warning
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The warning
+    pub fn warning(&self, message: &str) {
+        self.emit(MessageKind::Warning, message);
+        self.warnings.borrow_mut().push(message.to_string());
+    }
+
+    /// Print and record a failed part of a step that does not abort the command
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - What has failed
+    pub fn failure(&self, message: &str) {
+        self.emit(MessageKind::Failure, message);
+        self.warnings.borrow_mut().push(message.to_string());
+    }
+
+    /// Print a detail of a step in verbose mode (e.g. a written file or a network request)
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The detail
+    pub fn detail(&self, message: &str) {
+        self.emit(MessageKind::Detail, message);
+    }
+
+    /// Print the heading of a section of the summary
+    ///
+    /// # Arguments
+    ///
+    /// * `heading` - The heading (e.g. Next steps)
+    pub fn heading(&self, heading: &str) {
+        self.emit(MessageKind::Heading, heading);
+    }
+
+    /// Print an item of a section of the summary
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The item
+    pub fn item(&self, item: impl Display) {
+        self.emit(MessageKind::Item, &item.to_string());
+    }
+
+    /// Print the final message of a successful command
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message (e.g. Project successfully created!)
+    pub fn done(&self, message: &str) {
+        self.emit(MessageKind::Done, message);
+    }
+
+    /// The warnings recorded so far
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - The warnings
+    pub fn warnings(&self) -> Vec<String> {
+        self.warnings.borrow().clone()
+    }
+
+    /// Print the result of a successful command in JSON mode
+    ///
+    /// The result is extended by the status, the command and the recorded warnings. In text
+    /// mode nothing is printed, the progress already described the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The name of the command (e.g. create, templates list)
+    /// * `result` - The fields of the result
+    ///
+    /// # Errors
+    ///
+    /// * If the result cannot be serialized
+    pub fn result(
+        &self,
+        command: &str,
+        result: impl serde::Serialize,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if self.is_json() {
+            println!(
+                "{}",
+                self.document("ok", command, serde_json::to_value(result)?)?
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Print the error of a failed command in JSON mode
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The name of the command
+    /// * `error` - The error the command failed with
+    pub fn error(&self, command: &str, error: &dyn std::error::Error) {
+        let result = serde_json::json!({ "error": error.to_string() });
+        if let Ok(document) = self.document("error", command, result) {
+            println!("{}", document);
+        }
+    }
+
+    /// Build the JSON document of a result
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - Either `ok` or `error`
+    /// * `command` - The name of the command
+    /// * `result` - The fields of the result, must be an object or null
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, Box<dyn std::error::Error>>` - The pretty printed document
+    fn document(
+        &self,
+        status: &str,
+        command: &str,
+        result: Value,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let mut document = Map::new();
+        document.insert("status".to_string(), status.into());
+        document.insert("command".to_string(), command.into());
+        match result {
+            Value::Object(fields) => document.extend(fields),
+            Value::Null => {}
+            _ => return Err("The result of a command must be a JSON object".into()),
+        }
+        document.insert("warnings".to_string(), self.warnings().into());
+
+        Ok(serde_json::to_string_pretty(&document)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_document_contains_warnings() {
+        let output = Output::new(OutputFormat::Json);
+        output.clone().warning("Skipped existing file README.md");
+
+        let document = output
+            .document("ok", "create", serde_json::json!({ "project": "demo" }))
+            .unwrap();
+        let document: Value = serde_json::from_str(&document).unwrap();
+
+        assert_eq!(document["status"], "ok");
+        assert_eq!(document["command"], "create");
+        assert_eq!(document["project"], "demo");
+        assert_eq!(document["warnings"][0], "Skipped existing file README.md");
+        assert!(output.document("ok", "create", Value::from(1)).is_err());
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingReporter(RefCell<Vec<(MessageKind, String)>>);
+
+    impl Reporter for RecordingReporter {
+        fn report(&self, kind: MessageKind, message: &str) {
+            self.0.borrow_mut().push((kind, message.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_verbosity() {
+        let report = |verbosity: Verbosity| {
+            let reporter = Rc::new(RecordingReporter::default());
+            let output = Output::new(OutputFormat::Text)
+                .with_verbosity(verbosity)
+                .with_reporter(reporter.clone());
+            output.step("Creating project files...");
+            output.detail("Wrote src/main.rs");
+            output.warning("Skipped hook");
+            output.print("- rust");
+
+            let kinds: Vec<MessageKind> =
+                reporter.0.borrow().iter().map(|(kind, _)| *kind).collect();
+            kinds
+        };
+
+        assert_eq!(
+            report(Verbosity::Quiet),
+            vec![MessageKind::Warning, MessageKind::Print]
+        );
+        assert_eq!(
+            report(Verbosity::Normal),
+            vec![MessageKind::Step, MessageKind::Warning, MessageKind::Print]
+        );
+        assert_eq!(report(Verbosity::Verbose).len(), 4);
+    }
+
+    #[test]
+    fn test_strip_colors() {
+        assert_eq!(strip_colors(&"done".green().to_string()), "done");
+        assert_eq!(strip_colors("plain"), "plain");
+    }
+}
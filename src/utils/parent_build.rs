@@ -0,0 +1,492 @@
+use std::path::{Component, Path, PathBuf};
+
+use toml_edit::{Array, DocumentMut, Item, Value};
+
+/// A build that aggregates several projects and the file that lists its members
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParentBuild {
+    /// A Cargo.toml with a `[workspace]` section
+    CargoWorkspace(PathBuf),
+    /// A go.work file
+    GoWorkspace(PathBuf),
+    /// A pom.xml with `pom` packaging
+    MavenParent(PathBuf),
+}
+
+impl ParentBuild {
+    /// Detect the nearest parent build of a project that can contain it
+    ///
+    /// Only builds of the language of the project are detected: Cargo workspaces for Rust,
+    /// go.work files for Go and Maven parent poms for Java.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - The programming language of the project
+    /// * `project_path` - The absolute path of the project
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Self>` - The parent build if there is one
+    pub fn detect(language: &str, project_path: &Path) -> Option<Self> {
+        let mut ancestors = project_path.ancestors().skip(1);
+
+        match language.to_lowercase().as_str() {
+            "rust" => ancestors.find_map(|dir| {
+                let manifest = dir.join("Cargo.toml");
+                let document = std::fs::read_to_string(&manifest)
+                    .ok()?
+                    .parse::<DocumentMut>()
+                    .ok()?;
+                document
+                    .contains_table("workspace")
+                    .then_some(Self::CargoWorkspace(manifest))
+            }),
+            "go" => ancestors
+                .map(|dir| dir.join("go.work"))
+                .find(|manifest| manifest.exists())
+                .map(Self::GoWorkspace),
+            "java" => {
+                let manifest = ancestors
+                    .map(|dir| dir.join("pom.xml"))
+                    .find(|manifest| manifest.exists())?;
+                let content = std::fs::read_to_string(&manifest).ok()?;
+                let document = roxmltree::Document::parse(&content).ok()?;
+                let packaging = document
+                    .root_element()
+                    .children()
+                    .find(|node| node.has_tag_name("packaging"))?;
+
+                (packaging.text().map(str::trim) == Some("pom"))
+                    .then_some(Self::MavenParent(manifest))
+            }
+            _ => None,
+        }
+    }
+
+    /// The file that lists the members of the build
+    ///
+    /// # Returns
+    ///
+    /// * `&Path` - The path of the Cargo.toml, go.work or pom.xml
+    pub fn manifest(&self) -> &Path {
+        match self {
+            Self::CargoWorkspace(manifest)
+            | Self::GoWorkspace(manifest)
+            | Self::MavenParent(manifest) => manifest,
+        }
+    }
+
+    /// A human readable name of the kind of build
+    ///
+    /// # Returns
+    ///
+    /// * `&'static str` - The name (e.g. Cargo workspace)
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::CargoWorkspace(_) => "Cargo workspace",
+            Self::GoWorkspace(_) => "Go workspace",
+            Self::MavenParent(_) => "Maven parent",
+        }
+    }
+
+    /// Register a project as member of the build
+    ///
+    /// The manifest is edited in place and keeps its formatting and comments.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_path` - The absolute path of the project
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, Box<dyn std::error::Error>>` - Whether the project was added, `false` if it already was a member
+    ///
+    /// # Errors
+    ///
+    /// * If the project is not inside the directory of the build
+    /// * If the manifest cannot be read, parsed or written
+    pub fn register(&self, project_path: &Path) -> Result<bool, Box<dyn std::error::Error>> {
+        let manifest = self.manifest();
+        let root = manifest
+            .parent()
+            .ok_or("The manifest has no parent directory")?;
+        let member = relative_member(root, project_path)?;
+        let content = std::fs::read_to_string(manifest)?;
+
+        let updated = match self {
+            Self::CargoWorkspace(_) => add_cargo_member(&content, &member)?,
+            Self::GoWorkspace(_) => add_go_module(&content, &member),
+            Self::MavenParent(_) => add_maven_module(&content, &member)?,
+        };
+
+        match updated {
+            Some(updated) => {
+                std::fs::write(manifest, updated)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+/// The path of a member relative to the root of the build with `/` separators
+///
+/// # Arguments
+///
+/// * `root` - The root directory of the build
+/// * `project_path` - The absolute path of the project
+///
+/// # Returns
+///
+/// * `Result<String, Box<dyn std::error::Error>>` - The relative path
+fn relative_member(root: &Path, project_path: &Path) -> Result<String, Box<dyn std::error::Error>> {
+    let relative = project_path.strip_prefix(root).map_err(|_| {
+        format!(
+            "{} is not inside {}",
+            project_path.display(),
+            root.display()
+        )
+    })?;
+
+    Ok(relative
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/"))
+}
+
+/// Add a member to the `[workspace]` members of a Cargo.toml
+///
+/// # Arguments
+///
+/// * `content` - The content of the Cargo.toml
+/// * `member` - The path of the member relative to the workspace
+///
+/// # Returns
+///
+/// * `Result<Option<String>, Box<dyn std::error::Error>>` - The new content, `None` if a member pattern already covers the member
+fn add_cargo_member(
+    content: &str,
+    member: &str,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let mut document = content.parse::<DocumentMut>()?;
+    let workspace = document["workspace"]
+        .as_table_like_mut()
+        .ok_or("The Cargo.toml has no [workspace] section")?;
+
+    let members = workspace
+        .entry("members")
+        .or_insert(Item::Value(Value::Array(Array::new())))
+        .as_array_mut()
+        .ok_or("The workspace members are not a list")?;
+
+    if members
+        .iter()
+        .filter_map(Value::as_str)
+        .any(|pattern| matches_pattern(pattern, member))
+    {
+        return Ok(None);
+    }
+
+    let multiline = members.iter().last().and_then(|last| {
+        let decor = last.decor();
+        let prefix = decor.prefix()?.as_str()?;
+        prefix.contains('\n').then(|| decor.clone())
+    });
+    match multiline {
+        Some(decor) => {
+            let mut value = Value::from(member);
+            *value.decor_mut() = decor;
+            members.push_formatted(value);
+        }
+        None => members.push(member),
+    }
+
+    Ok(Some(document.to_string()))
+}
+
+/// Check if a Cargo workspace member pattern matches a path
+///
+/// Each `*` matches any characters inside one path segment.
+///
+/// # Arguments
+///
+/// * `pattern` - The member pattern (e.g. crates/*)
+/// * `path` - The relative path of the member
+///
+/// # Returns
+///
+/// * `bool` - Whether the pattern matches the path
+fn matches_pattern(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.trim_end_matches('/').split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+
+    pattern.len() == path.len()
+        && pattern.iter().zip(&path).all(|(pattern, segment)| {
+            let mut parts = pattern.split('*');
+            let first = parts.next().unwrap_or_default();
+            let Some(mut rest) = segment.strip_prefix(first) else {
+                return false;
+            };
+            let parts: Vec<&str> = parts.collect();
+            for (index, part) in parts.iter().enumerate() {
+                if index == parts.len() - 1 {
+                    return rest.ends_with(part);
+                }
+                match rest.find(part) {
+                    Some(position) => rest = &rest[position + part.len()..],
+                    None => return false,
+                }
+            }
+            rest.is_empty()
+        })
+}
+
+/// Add a module to the `use` directives of a go.work file
+///
+/// # Arguments
+///
+/// * `content` - The content of the go.work file
+/// * `module` - The path of the module relative to the go.work file
+///
+/// # Returns
+///
+/// * `Option<String>` - The new content, `None` if the module is already used
+fn add_go_module(content: &str, module: &str) -> Option<String> {
+    let normalize = |path: &str| {
+        path.trim()
+            .trim_start_matches("./")
+            .trim_end_matches('/')
+            .to_string()
+    };
+
+    let mut in_block = false;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or_default().trim();
+        let used = if in_block {
+            in_block = line != ")";
+            line
+        } else if line == "use (" {
+            in_block = true;
+            continue;
+        } else if let Some(path) = line.strip_prefix("use ") {
+            path
+        } else {
+            continue;
+        };
+
+        if normalize(used) == module {
+            return None;
+        }
+    }
+
+    let mut lines: Vec<&str> = content.lines().collect();
+    let entry = format!("\t./{}", module);
+    if let Some(start) = lines.iter().position(|line| line.trim() == "use (") {
+        let end = lines[start..]
+            .iter()
+            .position(|line| line.trim() == ")")
+            .map(|offset| start + offset)
+            .unwrap_or(lines.len());
+        lines.insert(end, &entry);
+        return Some(lines.join("\n") + "\n");
+    }
+
+    let separator = if content.is_empty() || content.ends_with('\n') {
+        ""
+    } else {
+        "\n"
+    };
+    Some(format!("{}{}\nuse ./{}\n", content, separator, module))
+}
+
+/// Add a module to the `<modules>` of a Maven parent pom
+///
+/// # Arguments
+///
+/// * `content` - The content of the pom.xml
+/// * `module` - The path of the module relative to the pom.xml
+///
+/// # Returns
+///
+/// * `Result<Option<String>, Box<dyn std::error::Error>>` - The new content, `None` if the module is already listed
+fn add_maven_module(
+    content: &str,
+    module: &str,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let document = roxmltree::Document::parse(content)?;
+    let project = document.root_element();
+    let line_start = |position: usize| content[..position].rfind('\n').map_or(0, |index| index + 1);
+    let indent = |position: usize| {
+        let start = line_start(position);
+        content[start..]
+            .chars()
+            .take_while(|char| char.is_whitespace() && *char != '\n')
+            .collect::<String>()
+    };
+
+    let mut updated = content.to_string();
+    match project.children().find(|node| node.has_tag_name("modules")) {
+        Some(modules) if content[modules.range()].ends_with("/>") => {
+            let element_indent = indent(modules.range().start);
+            updated.replace_range(
+                modules.range(),
+                &format!(
+                    "<modules>\n{0}{0}<module>{1}</module>\n{0}</modules>",
+                    element_indent, module
+                ),
+            );
+        }
+        Some(modules) => {
+            let listed = modules
+                .children()
+                .filter(|node| node.has_tag_name("module"))
+                .collect::<Vec<_>>();
+            if listed
+                .iter()
+                .any(|node| node.text().map(str::trim) == Some(module))
+            {
+                return Ok(None);
+            }
+
+            let closing = content[..modules.range().end]
+                .rfind("</")
+                .ok_or("Invalid <modules> element")?;
+            let start = line_start(closing);
+            if content[start..closing].trim().is_empty() {
+                let module_indent = match listed.last() {
+                    Some(last) => indent(last.range().start),
+                    None => format!("{}    ", indent(closing)),
+                };
+                updated.insert_str(
+                    start,
+                    &format!("{}<module>{}</module>\n", module_indent, module),
+                );
+            } else {
+                updated.insert_str(closing, &format!("<module>{}</module>", module));
+            }
+        }
+        None => {
+            let closing = content[..project.range().end]
+                .rfind("</")
+                .ok_or("Invalid <project> element")?;
+            let start = line_start(closing);
+            let child_indent = project
+                .children()
+                .find(|node| node.is_element())
+                .map(|node| indent(node.range().start))
+                .unwrap_or_else(|| "    ".to_string());
+            let module_indent = format!("{}{}", child_indent, child_indent);
+            updated.insert_str(
+                start,
+                &format!(
+                    "{0}<modules>\n{1}<module>{2}</module>\n{0}</modules>\n",
+                    child_indent, module_indent, module
+                ),
+            );
+        }
+    }
+
+    Ok(Some(updated))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_temp_dir;
+
+    #[test]
+    fn test_add_cargo_member() {
+        let content = "[workspace]\nmembers = [\n    \"core\",\n    \"cli\",\n]\n\n[workspace.package]\nversion = \"0.1.0\"\n";
+        assert_eq!(
+            add_cargo_member(content, "server").unwrap().unwrap(),
+            "[workspace]\nmembers = [\n    \"core\",\n    \"cli\",\n    \"server\",\n]\n\n[workspace.package]\nversion = \"0.1.0\"\n"
+        );
+
+        assert_eq!(
+            add_cargo_member("[workspace]\nresolver = \"2\"\n", "server")
+                .unwrap()
+                .unwrap(),
+            "[workspace]\nresolver = \"2\"\nmembers = [\"server\"]\n"
+        );
+
+        let content = "[workspace]\nmembers = [\"crates/*\"]\n";
+        assert_eq!(add_cargo_member(content, "crates/server").unwrap(), None);
+        assert!(add_cargo_member("[package]\nname = \"a\"\n", "server").is_err());
+
+        assert!(matches_pattern("crates/app-*", "crates/app-server"));
+        assert!(!matches_pattern("crates/app-*", "crates/server"));
+        assert!(!matches_pattern("crates/*", "crates/server/sub"));
+    }
+
+    #[test]
+    fn test_add_go_module() {
+        let content = "go 1.22\n\nuse (\n\t./api\n\t./worker // jobs\n)\n";
+        assert_eq!(
+            add_go_module(content, "backend").unwrap(),
+            "go 1.22\n\nuse (\n\t./api\n\t./worker // jobs\n\t./backend\n)\n"
+        );
+        assert_eq!(add_go_module(content, "worker"), None);
+
+        assert_eq!(
+            add_go_module("go 1.22\n\nuse ./api\n", "backend").unwrap(),
+            "go 1.22\n\nuse ./api\n\nuse ./backend\n"
+        );
+        assert_eq!(add_go_module("go 1.22\n\nuse ./api\n", "api"), None);
+    }
+
+    #[test]
+    fn test_add_maven_module() {
+        let content = "<project>\n  <packaging>pom</packaging>\n  <modules>\n    <module>core</module>\n  </modules>\n</project>\n";
+        assert_eq!(
+            add_maven_module(content, "web").unwrap().unwrap(),
+            "<project>\n  <packaging>pom</packaging>\n  <modules>\n    <module>core</module>\n    <module>web</module>\n  </modules>\n</project>\n"
+        );
+        assert_eq!(add_maven_module(content, "core").unwrap(), None);
+
+        let content = "<project>\n  <packaging>pom</packaging>\n</project>\n";
+        assert_eq!(
+            add_maven_module(content, "web").unwrap().unwrap(),
+            "<project>\n  <packaging>pom</packaging>\n  <modules>\n    <module>web</module>\n  </modules>\n</project>\n"
+        );
+
+        let content = "<project>\n  <modules/>\n  <build>\n    <finalName>app</finalName>\n  </build>\n</project>\n";
+        assert_eq!(
+            add_maven_module(content, "web").unwrap().unwrap(),
+            "<project>\n  <modules>\n    <module>web</module>\n  </modules>\n  <build>\n    <finalName>app</finalName>\n  </build>\n</project>\n"
+        );
+    }
+
+    #[test]
+    fn test_detect_and_register() {
+        let dir = create_temp_dir("scaffer_test_parent_build").unwrap();
+        std::fs::create_dir_all(dir.join("crates/server/src")).unwrap();
+        std::fs::write(
+            dir.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"core\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("crates/server/Cargo.toml"),
+            "[package]\nname = \"server\"\n",
+        )
+        .unwrap();
+        let project_path = dir.join("crates/server");
+
+        assert_eq!(ParentBuild::detect("Go", &project_path), None);
+        let parent = ParentBuild::detect("Rust", &project_path).unwrap();
+        assert_eq!(parent, ParentBuild::CargoWorkspace(dir.join("Cargo.toml")));
+
+        assert!(parent.register(&project_path).unwrap());
+        assert!(!parent.register(&project_path).unwrap());
+        assert_eq!(
+            std::fs::read_to_string(dir.join("Cargo.toml")).unwrap(),
+            "[workspace]\nmembers = [\"core\", \"crates/server\"]\n"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
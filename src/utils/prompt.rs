@@ -0,0 +1,133 @@
+use std::io::IsTerminal;
+
+use inquire::{Confirm, InquireError};
+
+/// Decides how the user is asked for missing values
+///
+/// In non-interactive mode no prompt is shown: confirmations take their default (or `yes`
+/// with `--yes`) and missing required values fail with a descriptive error.
+#[derive(Debug, Clone, Copy)]
+pub struct Prompter {
+    pub interactive: bool,
+    pub assume_yes: bool,
+}
+
+impl Prompter {
+    /// Create a new prompter from the global command line flags
+    ///
+    /// The prompter is non-interactive if one of the flags is set or if stdin is not a terminal.
+    ///
+    /// # Arguments
+    ///
+    /// * `yes` - Answer all confirmations with yes
+    /// * `no_input` - Never prompt and take the default answers
+    ///
+    /// # Returns
+    ///
+    /// The prompter
+    pub fn new(yes: bool, no_input: bool) -> Self {
+        Self {
+            interactive: !yes && !no_input && std::io::stdin().is_terminal(),
+            assume_yes: yes,
+        }
+    }
+
+    /// Ask the user for a confirmation
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The question to ask
+    /// * `default` - The answer used in non-interactive mode
+    ///
+    /// # Returns
+    ///
+    /// The answer of the user
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the prompt fails
+    pub fn confirm(
+        &self,
+        message: &str,
+        default: bool,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        if !self.interactive {
+            return Ok(self.assume_yes || default);
+        }
+
+        Ok(Confirm::new(message).with_default(default).prompt()?)
+    }
+
+    /// Take the given value or prompt the user for it
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value given on the command line
+    /// * `flag` - The command line flag to mention if the value is missing
+    /// * `prompt` - The prompt to ask the user for the value
+    ///
+    /// # Returns
+    ///
+    /// The given or prompted value
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the value is missing in non-interactive mode or if the prompt fails
+    pub fn require<T>(
+        &self,
+        value: Option<T>,
+        flag: &str,
+        prompt: impl FnOnce() -> Result<T, InquireError>,
+    ) -> Result<T, Box<dyn std::error::Error>> {
+        if let Some(value) = value {
+            return Ok(value);
+        }
+
+        if !self.interactive {
+            return Err(format!(
+                "Missing required value {} (running without interactive input)",
+                flag
+            )
+            .into());
+        }
+
+        Ok(prompt()?)
+    }
+}
+
+impl Default for Prompter {
+    fn default() -> Self {
+        Self::new(false, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_interactive_prompter() {
+        let prompter = Prompter::new(false, true);
+
+        assert!(!prompter.interactive);
+        assert!(prompter.confirm("Continue?", true).unwrap());
+        assert!(!prompter.confirm("Continue?", false).unwrap());
+        assert_eq!(
+            prompter
+                .require(Some("rust".to_string()), "--language", || unreachable!())
+                .unwrap(),
+            "rust"
+        );
+        assert!(prompter
+            .require::<String>(None, "--language", || unreachable!())
+            .is_err());
+    }
+
+    #[test]
+    fn test_assume_yes_prompter() {
+        let prompter = Prompter::new(true, false);
+
+        assert!(!prompter.interactive);
+        assert!(prompter.confirm("Continue?", false).unwrap());
+    }
+}
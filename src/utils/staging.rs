@@ -0,0 +1,247 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Mutex, Once},
+};
+
+/// The staging directories and the parent directories created for them that have to be
+/// removed when the process is interrupted
+static ACTIVE_STAGINGS: Mutex<Vec<(PathBuf, Vec<PathBuf>)>> = Mutex::new(Vec::new());
+static INTERRUPT_HANDLER: Once = Once::new();
+
+/// A staging directory in which a project is generated before it is moved into place
+///
+/// The staging directory is removed when the value is dropped without being committed,
+/// so a failed or interrupted generation leaves nothing behind. This includes the parent
+/// directories that were created for it.
+pub struct Staging {
+    path: PathBuf,
+    target: PathBuf,
+    created_parents: Vec<PathBuf>,
+    committed: bool,
+}
+
+impl Staging {
+    /// Create a new staging directory next to the given target
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The final path of the project
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, Box<dyn std::error::Error>>` - The created staging directory
+    ///
+    /// # Errors
+    ///
+    /// * If the staging directory cannot be created
+    pub fn new(target: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let parent = target
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let name = target
+            .file_name()
+            .ok_or("Invalid project path")?
+            .to_string_lossy();
+        let path = parent.join(format!(".{}.scaffer-{}", name, std::process::id()));
+
+        if path.exists() {
+            std::fs::remove_dir_all(&path)?;
+        }
+        let created_parents = parent
+            .ancestors()
+            .take_while(|ancestor| !ancestor.as_os_str().is_empty() && !ancestor.exists())
+            .map(Path::to_path_buf)
+            .collect::<Vec<_>>();
+        std::fs::create_dir_all(parent)?;
+        std::fs::create_dir(&path)?;
+
+        Self::register(&path, &created_parents);
+
+        Ok(Self {
+            path,
+            target: target.to_path_buf(),
+            created_parents,
+            committed: false,
+        })
+    }
+
+    /// The path of the staging directory
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Move the staged project into its final place
+    ///
+    /// If the target does not exist yet, the staging directory is renamed. Otherwise every
+    /// staged file is moved into the existing target.
+    ///
+    /// # Errors
+    ///
+    /// * If the staged files cannot be moved
+    pub fn commit(mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.target.exists() {
+            Self::move_contents(&self.path, &self.target)?;
+            std::fs::remove_dir_all(&self.path)?;
+        } else {
+            std::fs::rename(&self.path, &self.target)?;
+        }
+
+        self.committed = true;
+        Self::unregister(&self.path);
+
+        Ok(())
+    }
+
+    /// Move the contents of a directory into another directory recursively
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The source directory
+    /// * `to` - The destination directory
+    ///
+    /// # Errors
+    ///
+    /// * If a directory cannot be created or a file cannot be moved
+    fn move_contents(from: &Path, to: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::create_dir_all(to)?;
+
+        for entry in std::fs::read_dir(from)? {
+            let entry = entry?;
+            let dest = to.join(entry.file_name());
+            if entry.file_type()?.is_dir() {
+                Self::move_contents(&entry.path(), &dest)?;
+            } else {
+                std::fs::rename(entry.path(), dest)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove a staging directory and the parent directories created for it
+    ///
+    /// Parent directories are only removed while they are empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The staging directory
+    /// * `created_parents` - The created parent directories, the innermost first
+    fn remove(path: &Path, created_parents: &[PathBuf]) {
+        let _ = std::fs::remove_dir_all(path);
+        for parent in created_parents {
+            if std::fs::remove_dir(parent).is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Register a staging directory for the cleanup on interruption
+    ///
+    /// An interruption only ends the process while a staging directory is active. Otherwise
+    /// it is left to the running child processes, e.g. the task started by `create --run`.
+    fn register(path: &Path, created_parents: &[PathBuf]) {
+        INTERRUPT_HANDLER.call_once(|| {
+            let _ = ctrlc::set_handler(|| {
+                let Ok(mut stagings) = ACTIVE_STAGINGS.lock() else {
+                    return;
+                };
+                if stagings.is_empty() {
+                    return;
+                }
+                for (path, created_parents) in stagings.drain(..) {
+                    Self::remove(&path, &created_parents);
+                }
+                std::process::exit(130);
+            });
+        });
+
+        if let Ok(mut stagings) = ACTIVE_STAGINGS.lock() {
+            stagings.push((path.to_path_buf(), created_parents.to_vec()));
+        }
+    }
+
+    /// Remove a staging directory from the cleanup on interruption
+    fn unregister(path: &Path) {
+        if let Ok(mut stagings) = ACTIVE_STAGINGS.lock() {
+            stagings.retain(|(staging, _)| staging != path);
+        }
+    }
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        if !self.committed {
+            Self::unregister(&self.path);
+            Self::remove(&self.path, &self.created_parents);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_temp_dir;
+
+    #[test]
+    fn test_staging_is_removed_on_drop() {
+        let root = create_temp_dir("scaffer_test_staging_drop").unwrap();
+        let target = root.join("demo");
+
+        let staging = Staging::new(&target).unwrap();
+        let staging_path = staging.path().to_path_buf();
+        std::fs::write(staging_path.join("main.rs"), "").unwrap();
+        drop(staging);
+
+        assert!(!staging_path.exists());
+        assert!(!target.exists());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_staging_removes_created_parents() {
+        let root = create_temp_dir("scaffer_test_staging_parents").unwrap();
+
+        let staging = Staging::new(&root.join("a/b/demo")).unwrap();
+        let staging_path = staging.path().to_path_buf();
+        assert!(root.join("a/b").is_dir());
+        drop(staging);
+
+        assert!(!root.join("a").exists());
+        assert!(root.exists());
+        assert!(!ACTIVE_STAGINGS
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(path, _)| path == &staging_path));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_staging_commit_into_existing_target() {
+        let root = create_temp_dir("scaffer_test_staging_commit").unwrap();
+        let target = root.join("demo");
+        std::fs::create_dir_all(target.join("src")).unwrap();
+        std::fs::write(target.join("README.md"), "keep").unwrap();
+
+        let staging = Staging::new(&target).unwrap();
+        std::fs::create_dir_all(staging.path().join("src")).unwrap();
+        std::fs::write(staging.path().join("src/main.rs"), "fn main() {}").unwrap();
+        let staging_path = staging.path().to_path_buf();
+        staging.commit().unwrap();
+
+        assert!(!staging_path.exists());
+        assert_eq!(
+            std::fs::read_to_string(target.join("README.md")).unwrap(),
+            "keep"
+        );
+        assert_eq!(
+            std::fs::read_to_string(target.join("src/main.rs")).unwrap(),
+            "fn main() {}"
+        );
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,292 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+    process::ExitStatus,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::shell_command;
+
+/// The name of the file the tasks of a project are stored in
+pub const TASK_FILE: &str = "scaffer.toml";
+
+/// The named commands of a generated project (e.g. build, test, run)
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskFile {
+    #[serde(default)]
+    pub tasks: BTreeMap<String, String>,
+}
+
+/// The outcome of a task that has been run
+#[derive(Debug, Clone, Serialize)]
+pub struct TaskRun {
+    pub task: String,
+    pub command: String,
+    pub exit_code: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stdout: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>,
+}
+
+/// A task that exited with a non-zero status
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskFailed {
+    pub command: String,
+    pub code: i32,
+}
+
+impl Display for TaskFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` failed with exit code {}", self.command, self.code)
+    }
+}
+
+impl std::error::Error for TaskFailed {}
+
+impl TaskFile {
+    /// Render the task file as TOML
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, Box<dyn std::error::Error>>` - The content of the task file
+    pub fn render(&self) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(format!(
+            "# The tasks of the project, run them with `scaffer run <task>`\n\n{}",
+            toml::to_string(self)?
+        ))
+    }
+
+    /// Find the task file in the given directory or one of its parents
+    ///
+    /// # Arguments
+    ///
+    /// * `start_dir` - The directory to start the search in
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(PathBuf, Self), Box<dyn std::error::Error>>` - The directory of the task file and its tasks
+    ///
+    /// # Errors
+    ///
+    /// * If no task file is found
+    /// * If the task file cannot be parsed
+    pub fn find(start_dir: &Path) -> Result<(PathBuf, Self), Box<dyn std::error::Error>> {
+        let dir = start_dir
+            .ancestors()
+            .find(|dir| dir.join(TASK_FILE).is_file())
+            .ok_or_else(|| {
+                format!(
+                    "No {} found in {} or its parents",
+                    TASK_FILE,
+                    start_dir.display()
+                )
+            })?;
+
+        let path = dir.join(TASK_FILE);
+        let content = std::fs::read_to_string(&path)?;
+        let tasks = toml::from_str(&content)
+            .map_err(|err| format!("Could not parse {}: {}", path.display(), err))?;
+
+        Ok((dir.to_path_buf(), tasks))
+    }
+
+    /// The command line of a task with the given arguments appended
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The name of the task
+    /// * `args` - Additional arguments for the command, quoted for the shell
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, Box<dyn std::error::Error>>` - The command line to run
+    ///
+    /// # Errors
+    ///
+    /// * If the project has no task with the given name
+    pub fn command(
+        &self,
+        task: &str,
+        args: &[String],
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let command = self.tasks.get(task).ok_or_else(|| {
+            format!(
+                "Unknown task {}, available tasks: {}",
+                task,
+                self.tasks.keys().cloned().collect::<Vec<_>>().join(", ")
+            )
+        })?;
+
+        Ok(std::iter::once(command.clone())
+            .chain(args.iter().map(|arg| quote(arg)))
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+}
+
+impl TaskRun {
+    /// Run the command of a task with the system shell
+    ///
+    /// # Arguments
+    ///
+    /// * `task` - The name of the task
+    /// * `command` - The command line of the task
+    /// * `working_dir` - The directory to run the command in
+    /// * `capture` - Capture the output of the command instead of streaming it to the terminal
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Self, Box<dyn std::error::Error>>` - The outcome of the task
+    ///
+    /// # Errors
+    ///
+    /// * If the command cannot be started
+    pub fn execute(
+        task: &str,
+        command: &str,
+        working_dir: &Path,
+        capture: bool,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut process = shell_command(command);
+        process.current_dir(working_dir);
+
+        let (exit_code, stdout, stderr) = if capture {
+            let output = process.output()?;
+            (
+                exit_code(output.status),
+                Some(String::from_utf8_lossy(&output.stdout).to_string()),
+                Some(String::from_utf8_lossy(&output.stderr).to_string()),
+            )
+        } else {
+            (exit_code(process.status()?), None, None)
+        };
+
+        Ok(Self {
+            task: task.to_string(),
+            command: command.to_string(),
+            exit_code,
+            stdout,
+            stderr,
+        })
+    }
+
+    /// Check that the task exited successfully
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), TaskFailed>` - The result of the task
+    ///
+    /// # Errors
+    ///
+    /// * If the task exited with a non-zero status
+    pub fn check(&self) -> Result<(), TaskFailed> {
+        if self.exit_code == 0 {
+            return Ok(());
+        }
+
+        Err(TaskFailed {
+            command: self.command.clone(),
+            code: self.exit_code,
+        })
+    }
+}
+
+/// The exit code of a finished process
+///
+/// A process killed by a signal reports `128 + signal` on Unix, like the shell does.
+///
+/// # Arguments
+///
+/// * `status` - The exit status of the process
+///
+/// # Returns
+///
+/// The exit code of the process
+fn exit_code(status: ExitStatus) -> i32 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+
+        if let Some(signal) = status.signal() {
+            return 128 + signal;
+        }
+    }
+
+    status.code().unwrap_or(1)
+}
+
+/// Quote an argument so the shell passes it on unchanged
+///
+/// # Arguments
+///
+/// * `arg` - The argument to quote
+///
+/// # Returns
+///
+/// The quoted argument
+fn quote(arg: &str) -> String {
+    if cfg!(windows) {
+        format!("\"{}\"", arg.replace('"', "\\\""))
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::create_temp_dir;
+
+    #[test]
+    fn test_render_and_find() {
+        let dir = create_temp_dir("scaffer_test_find_tasks").unwrap();
+        std::fs::create_dir_all(dir.join("src/nested")).unwrap();
+
+        let tasks = TaskFile {
+            tasks: BTreeMap::from([
+                ("build".to_string(), "cargo build".to_string()),
+                ("test".to_string(), "cargo test".to_string()),
+            ]),
+        };
+        let content = tasks.render().unwrap();
+        assert!(content.starts_with("# The tasks of the project"));
+        assert!(content.contains("[tasks]\nbuild = \"cargo build\"\ntest = \"cargo test\"\n"));
+        std::fs::write(dir.join(TASK_FILE), content).unwrap();
+
+        let (found, loaded) = TaskFile::find(&dir.join("src/nested")).unwrap();
+        assert_eq!(found, dir);
+        assert_eq!(loaded, tasks);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_killed_task() {
+        let run = TaskRun::execute("kill", "kill -TERM $$", &std::env::temp_dir(), true).unwrap();
+        assert_eq!(run.exit_code, 143);
+        assert_eq!(run.check().unwrap_err().code, 143);
+    }
+
+    #[test]
+    fn test_command() {
+        let tasks = TaskFile {
+            tasks: BTreeMap::from([("test".to_string(), "cargo test".to_string())]),
+        };
+
+        assert_eq!(tasks.command("test", &[]).unwrap(), "cargo test");
+        assert_eq!(
+            tasks
+                .command("test", &["--".to_string(), "it's".to_string()])
+                .unwrap(),
+            "cargo test '--' 'it'\\''s'"
+        );
+        assert_eq!(
+            tasks.command("lint", &[]).unwrap_err().to_string(),
+            "Unknown task lint, available tasks: test"
+        );
+    }
+}
@@ -0,0 +1,384 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{clone_repository, Output, Template};
+
+/// The git repository an installed template comes from
+///
+/// It is written as `<repository>[#ref][:subdir]` on the command line, e.g.
+/// `https://github.com/acme/templates.git#v2:rust` or `/srv/git/templates.git`.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct TemplateSource {
+    pub repository: String,
+    #[serde(default, rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub reference: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subdir: Option<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+/// A temporary clone of a template repository that is removed when it is dropped
+pub struct Checkout {
+    path: PathBuf,
+    commit: String,
+}
+
+impl Checkout {
+    /// The directory of the clone
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The commit that has been checked out
+    pub fn commit(&self) -> &str {
+        &self.commit
+    }
+}
+
+impl Drop for Checkout {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
+
+impl FromStr for TemplateSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (repository, reference, subdir) = match s.rsplit_once('#') {
+            Some((repository, rest)) => match rest.split_once(':') {
+                Some((reference, subdir)) => (repository, reference, subdir),
+                None => (repository, rest, ""),
+            },
+            None => match s.rsplit_once(':') {
+                Some((repository, subdir)) if Self::is_repository(repository, subdir) => {
+                    (repository, "", subdir)
+                }
+                _ => (s, "", ""),
+            },
+        };
+
+        if repository.is_empty() {
+            return Err(format!(
+                "Invalid template source {}, the repository is missing",
+                s
+            ));
+        }
+        if repository.starts_with('-') || reference.starts_with('-') {
+            return Err(format!(
+                "Invalid template source {}, the repository and ref must not start with -",
+                s
+            ));
+        }
+
+        Ok(Self {
+            repository: repository.to_string(),
+            reference: (!reference.is_empty()).then(|| reference.to_string()),
+            subdir: (!subdir.is_empty()).then(|| PathBuf::from(subdir)),
+            commit: None,
+        })
+    }
+}
+
+impl Display for TemplateSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.repository)?;
+        if let Some(reference) = &self.reference {
+            write!(f, "#{}", reference)?;
+        }
+        if let Some(subdir) = &self.subdir {
+            write!(f, ":{}", subdir.display())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl TemplateSource {
+    /// Whether the part before the last colon of a source is a complete repository
+    ///
+    /// The colon may also belong to the repository itself, e.g. `git@host:repo` or a port. A
+    /// repository in the scp-like syntax `[user@]host:path` already contains its colon.
+    ///
+    /// # Arguments
+    ///
+    /// * `repository` - The part before the last colon
+    /// * `subdir` - The part after the last colon
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the part after the colon is a subdirectory
+    fn is_repository(repository: &str, subdir: &str) -> bool {
+        !subdir.starts_with("//")
+            && (repository.ends_with(".git")
+                || Path::new(repository).exists()
+                || match repository.split_once("://") {
+                    Some((_, rest)) => rest.contains('/'),
+                    None => repository
+                        .split_once(':')
+                        .is_some_and(|(host, path)| !host.contains('/') && !path.is_empty()),
+                })
+    }
+
+    /// Resolve a local repository to an absolute path, so the source can be used from anywhere
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The source with an absolute repository path if it is a local directory
+    pub fn absolute(mut self) -> Self {
+        if let Ok(path) = Path::new(&self.repository).canonicalize() {
+            self.repository = path.display().to_string();
+        }
+        self
+    }
+
+    /// Clone the repository into a new temporary directory
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Checkout, Box<dyn std::error::Error>>` - The clone, removed when it is dropped
+    ///
+    /// # Errors
+    ///
+    /// * If no temporary directory can be created
+    /// * If the repository cannot be cloned or the reference does not exist
+    pub fn checkout(&self) -> Result<Checkout, Box<dyn std::error::Error>> {
+        let mut checkout = Checkout {
+            path: create_temp_dir("scaffer-install")?,
+            commit: String::new(),
+        };
+        checkout.commit =
+            clone_repository(&self.repository, self.reference.as_deref(), &checkout.path)?;
+
+        Ok(checkout)
+    }
+
+    /// Find the templates in the subdirectory of a checkout
+    ///
+    /// The subdirectory can be a single template file or a directory of template files.
+    /// JSON files that are no templates are skipped with a warning. Every template records
+    /// this source with the commit of the checkout.
+    ///
+    /// # Arguments
+    ///
+    /// * `checkout` - The clone of the repository
+    /// * `output` - The output to report skipped files to
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<(String, Template)>, Box<dyn std::error::Error>>` - The names and templates found
+    ///
+    /// # Errors
+    ///
+    /// * If the subdirectory does not exist
+    /// * If no template is found
+    pub fn templates(
+        &self,
+        checkout: &Checkout,
+        output: &Output,
+    ) -> Result<Vec<(String, Template)>, Box<dyn std::error::Error>> {
+        let subdir = self.subdir.clone().unwrap_or_default();
+        if subdir.is_absolute() || subdir.components().any(|c| c.as_os_str() == "..") {
+            return Err(format!("Invalid subdirectory {}", subdir.display()).into());
+        }
+
+        let root = checkout.path().join(&subdir);
+        let mut paths = if root.is_file() {
+            vec![root.clone()]
+        } else if root.is_dir() {
+            std::fs::read_dir(&root)?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+                .collect()
+        } else {
+            return Err(
+                format!("{} does not exist in {}", subdir.display(), self.repository).into(),
+            );
+        };
+        paths.sort();
+
+        let source = Self {
+            commit: Some(checkout.commit().to_string()),
+            ..self.clone()
+        };
+
+        let mut templates = vec![];
+        for path in paths {
+            let name = path
+                .file_stem()
+                .ok_or("Invalid template file")?
+                .to_string_lossy()
+                .to_lowercase();
+            match serde_json::from_str::<Template>(&std::fs::read_to_string(&path)?) {
+                Ok(mut template) => {
+                    template.source = Some(source.clone());
+                    templates.push((name, template));
+                }
+                Err(err) => output.warning(&format!(
+                    "Skipped {}, it is no template: {}",
+                    path.strip_prefix(checkout.path())
+                        .unwrap_or(&path)
+                        .display(),
+                    err
+                )),
+            }
+        }
+
+        if templates.is_empty() {
+            return Err(format!("No templates found in {}", self).into());
+        }
+
+        Ok(templates)
+    }
+}
+
+/// Create a new, empty temporary directory with a unique name
+///
+/// The directory is created exclusively, so an existing directory or link with the same name
+/// is never reused.
+///
+/// # Arguments
+///
+/// * `prefix` - The prefix of the directory name
+///
+/// # Returns
+///
+/// * `Result<PathBuf, Box<dyn std::error::Error>>` - The path of the created directory
+///
+/// # Errors
+///
+/// * If the directory cannot be created
+pub(crate) fn create_temp_dir(prefix: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    loop {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.subsec_nanos());
+        let path = std::env::temp_dir().join(format!(
+            "{}-{}-{}-{}",
+            prefix,
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed),
+            nanos
+        ));
+
+        match std::fs::create_dir(&path) {
+            Ok(()) => return Ok(path),
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::{init_repository, GitConfig};
+    use std::process::Command;
+
+    #[test]
+    fn test_parse_source() {
+        let parse = |s: &str| s.parse::<TemplateSource>().unwrap();
+
+        let source = parse("https://github.com/acme/templates.git#v2:rust");
+        assert_eq!(source.repository, "https://github.com/acme/templates.git");
+        assert_eq!(source.reference.as_deref(), Some("v2"));
+        assert_eq!(source.subdir, Some(PathBuf::from("rust")));
+
+        let source = parse("file:///srv/git/templates:lang/go");
+        assert_eq!(source.repository, "file:///srv/git/templates");
+        assert_eq!(source.reference, None);
+        assert_eq!(source.subdir, Some(PathBuf::from("lang/go")));
+
+        let source = parse("git@github.com:acme/templates#main");
+        assert_eq!(source.repository, "git@github.com:acme/templates");
+        assert_eq!(source.reference.as_deref(), Some("main"));
+        assert_eq!(source.subdir, None);
+
+        let source = parse("git@github.com:acme/templates:rust");
+        assert_eq!(source.repository, "git@github.com:acme/templates");
+        assert_eq!(source.reference, None);
+        assert_eq!(source.subdir, Some(PathBuf::from("rust")));
+        assert_eq!(parse("git@github.com:acme/templates").subdir, None);
+
+        assert_eq!(
+            parse("https://git.acme.com:8443/templates").repository,
+            "https://git.acme.com:8443/templates"
+        );
+        assert_eq!(
+            parse("/srv/templates.git#v1:rust").to_string(),
+            "/srv/templates.git#v1:rust"
+        );
+        assert!("#main".parse::<TemplateSource>().is_err());
+        assert!("/srv/templates.git#--orphan=x"
+            .parse::<TemplateSource>()
+            .is_err());
+        assert!("-uexploit#main".parse::<TemplateSource>().is_err());
+    }
+
+    #[test]
+    fn test_checkout_templates() {
+        let dir = create_temp_dir("scaffer_test_checkout_templates").unwrap();
+        let work = dir.join("work");
+        std::fs::create_dir_all(work.join("lang")).unwrap();
+        std::fs::write(
+            work.join("lang/Rust.json"),
+            r#"{ "structure": { "directories": [], "files": [] }, "start_command": "cargo run" }"#,
+        )
+        .unwrap();
+        std::fs::write(work.join("lang/package.json"), r#"{ "name": "tooling" }"#).unwrap();
+
+        let config = GitConfig {
+            default_branch: Some("main".to_string()),
+            author_name: Some("Scaffer".to_string()),
+            author_email: Some("scaffer@example.com".to_string()),
+            commit_message: None,
+        };
+        init_repository(&work, &config).unwrap();
+        let git = |dir: &Path, args: &[&str]| {
+            assert!(Command::new("git")
+                .current_dir(dir)
+                .args(args)
+                .status()
+                .unwrap()
+                .success());
+        };
+        git(&work, &["tag", "v1"]);
+        git(
+            &dir,
+            &["clone", "--quiet", "--bare", "work", "templates.git"],
+        );
+
+        let source: TemplateSource =
+            format!("file://{}#v1:lang", dir.join("templates.git").display())
+                .parse()
+                .unwrap();
+        let checkout = source.checkout().unwrap();
+        assert_eq!(checkout.commit().len(), 40);
+        assert_ne!(checkout.path(), source.checkout().unwrap().path());
+
+        let templates = source.templates(&checkout, &Output::default()).unwrap();
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].0, "rust");
+        let recorded = templates[0].1.source.clone().unwrap();
+        assert_eq!(recorded.reference.as_deref(), Some("v1"));
+        assert_eq!(recorded.commit.as_deref(), Some(checkout.commit()));
+
+        let path = checkout.path().to_path_buf();
+        drop(checkout);
+        assert!(!path.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,13 +1,37 @@
-use std::path::PathBuf;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 
 use serde::{Deserialize, Serialize};
 
-use super::Config;
+use super::{
+    has_significant_indentation, Config, EditorConfig, Formatting, Hooks, TaskFile, TemplateSource,
+};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Template {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
     pub structure: Structure,
     pub start_command: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tasks: BTreeMap<String, String>,
+    #[serde(default)]
+    pub hooks: Hooks,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gitignore: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub readme: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub generators: Vec<Generator>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<Member>,
+    #[serde(default, skip_serializing_if = "Formatting::is_default")]
+    pub formatting: Formatting,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<TemplateSource>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -20,26 +44,107 @@ pub struct Structure {
 pub struct File {
     pub path: PathBuf,
     pub content: Vec<String>,
+    #[serde(default, skip_serializing_if = "Formatting::is_default")]
+    pub formatting: Formatting,
+}
+
+/// A sub-generator that adds a component to an existing project
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Generator {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub files: Vec<File>,
+    #[serde(default)]
+    pub append: Vec<File>,
+}
+
+/// A project that is created inside a workspace
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub template: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+}
+
+impl Member {
+    /// The directory of the member relative to the workspace root
+    ///
+    /// # Returns
+    ///
+    /// * `PathBuf` - The path of the member joined with its name
+    pub fn directory(&self) -> PathBuf {
+        self.path.clone().unwrap_or_default().join(&self.name)
+    }
+}
+
+impl FromStr for Member {
+    type Err = String;
+
+    /// Parse a member from the `template:name` command line syntax
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((template, name)) if !template.is_empty() && !name.is_empty() => Ok(Self {
+                template: template.to_string(),
+                name: name.to_string(),
+                path: None,
+            }),
+            _ => Err(format!("Invalid member `{}`, use LANGUAGE:NAME", s)),
+        }
+    }
+}
+
+/// What a generator did with a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeneratedFile {
+    Created(PathBuf),
+    Appended(PathBuf),
+    Unchanged(PathBuf),
 }
 
 impl Template {
     /// Load the template for the given language
     ///
+    /// The template is looked up in the template directory and falls back to the templates
+    /// bundled with scaffer, so templates added in a newer version work on existing installs.
+    ///
     /// # Arguments
     ///
     /// * `language` - The programming language of the project
+    /// * `config` - The configuration object
     ///
     /// # Returns
     ///
     /// * `Result<Self, Box<dyn std::error::Error>>` - The result of the load template
-    pub fn load_template(language: String) -> Result<Self, Box<dyn std::error::Error>> {
-        let config = Config::load()?;
-        let template_path = config
-            .template_dir
-            .join(format!("{}.json", language.to_lowercase()));
+    ///
+    /// # Errors
+    ///
+    /// * If there is no template for the language
+    /// * If the template cannot be read or parsed
+    pub fn load_template(
+        language: String,
+        config: &Config,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let file_name = format!("{}.json", language.to_lowercase());
+        let template_path = [
+            config.template_dir.join(&file_name),
+            Config::bundled_template_dir().join(&file_name),
+        ]
+        .into_iter()
+        .find(|path| path.is_file())
+        .ok_or_else(|| {
+            format!(
+                "No template found for {}, expected {}",
+                language,
+                config.template_dir.join(&file_name).display()
+            )
+        })?;
 
-        let template = std::fs::read_to_string(template_path)?;
-        let template: Template = serde_json::from_str(&template)?;
+        let template = std::fs::read_to_string(&template_path)?;
+        let template: Template = serde_json::from_str(&template)
+            .map_err(|err| format!("Could not parse {}: {}", template_path.display(), err))?;
 
         Ok(template)
     }
@@ -59,30 +164,433 @@ impl Template {
         project_name: String,
         content: Vec<String>,
     ) -> Result<String, Box<dyn std::error::Error>> {
-        let mut content = content.join("\n");
+        self.render(
+            content,
+            &HashMap::from([("project_name".to_string(), project_name)]),
+        )
+    }
+
+    /// Run a generator of the template in an existing project
+    ///
+    /// The paths and contents of the generator are rendered with the given variables and
+    /// formatted like the files of a new project. New files are created, the content of the
+    /// append entries is added to the end of existing files unless it is already present.
+    ///
+    /// # Arguments
+    ///
+    /// * `generator` - The generator to run
+    /// * `project_path` - The root directory of the project
+    /// * `variables` - The variables of the component (e.g. name)
+    /// * `force` - Overwrite files that already exist
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<GeneratedFile>, Box<dyn std::error::Error>>` - The files the generator touched
+    ///
+    /// # Errors
+    ///
+    /// * If a file to create already exists and `force` is not set
+    /// * If an `.editorconfig` file cannot be read
+    /// * If a file cannot be written
+    pub fn run_generator(
+        &self,
+        generator: &Generator,
+        project_path: &Path,
+        variables: &HashMap<String, String>,
+        force: bool,
+    ) -> Result<Vec<GeneratedFile>, Box<dyn std::error::Error>> {
+        let editorconfig = self.editorconfig(project_path, variables)?;
+        let render_file = |file: &File| -> Result<(PathBuf, String), Box<dyn std::error::Error>> {
+            let path = PathBuf::from(
+                self.render(vec![file.path.to_string_lossy().to_string()], variables)?,
+            );
+            let content = self.format_content(
+                &path,
+                &self.render(file.content.clone(), variables)?,
+                project_path,
+                &editorconfig,
+                &file.formatting,
+            );
+            Ok((project_path.join(path), content))
+        };
+
+        let mut files = vec![];
+        for file in &generator.files {
+            files.push(render_file(file)?);
+        }
+
+        if !force {
+            if let Some((path, _)) = files.iter().find(|(path, _)| path.exists()) {
+                return Err(format!(
+                    "{} already exists. Use --force to overwrite it",
+                    path.display()
+                )
+                .into());
+            }
+        }
+
+        let mut generated = vec![];
+        for (path, content) in files {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, content)?;
+            generated.push(GeneratedFile::Created(path));
+        }
+
+        for file in &generator.append {
+            let (path, content) = render_file(file)?;
+            let existing = std::fs::read_to_string(&path).unwrap_or_default();
+
+            let lines = content.trim_end_matches(['\r', '\n']);
+            if existing.contains(lines) {
+                generated.push(GeneratedFile::Unchanged(path));
+                continue;
+            }
+
+            let separator = if existing.is_empty() || existing.ends_with('\n') {
+                ""
+            } else if content.ends_with("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            };
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, format!("{}{}{}", existing, separator, content))?;
+            generated.push(GeneratedFile::Appended(path));
+        }
+
+        Ok(generated)
+    }
+
+    /// The names of the gitignore templates to merge into the .gitignore file
+    ///
+    /// The names declared by the template (or the language if it declares none) are
+    /// followed by the additional names.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - The programming language of the project
+    /// * `extra` - Additional gitignore templates (e.g. JetBrains, macOS)
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - The names of the gitignore templates
+    pub fn gitignore_names(&self, language: &str, extra: &[String]) -> Vec<String> {
+        let mut names = if self.gitignore.is_empty() {
+            vec![language.to_string()]
+        } else {
+            self.gitignore.clone()
+        };
+        names.extend(extra.iter().cloned());
+
+        names
+    }
+
+    /// Load the `.editorconfig` settings that apply to the project
+    ///
+    /// The `.editorconfig` files of the project directory and its parents are extended by the
+    /// `.editorconfig` file of the template, if it ships one.
+    ///
+    /// # Arguments
+    ///
+    /// * `project_path` - The root directory of the project
+    /// * `variables` - The variables of the project
+    ///
+    /// # Returns
+    ///
+    /// * `Result<EditorConfig, Box<dyn std::error::Error>>` - The `.editorconfig` settings
+    ///
+    /// # Errors
+    ///
+    /// * If an `.editorconfig` file cannot be read
+    pub fn editorconfig(
+        &self,
+        project_path: &Path,
+        variables: &HashMap<String, String>,
+    ) -> Result<EditorConfig, Box<dyn std::error::Error>> {
+        let mut editorconfig = EditorConfig::load(project_path)?;
+        if let Some(file) = self
+            .structure
+            .files
+            .iter()
+            .find(|file| file.path == Path::new(".editorconfig"))
+        {
+            editorconfig.add(project_path, &self.render(file.content.clone(), variables)?);
+        }
+
+        Ok(editorconfig)
+    }
+
+    /// Normalize the line endings, the final newline and the indentation of a generated file
+    ///
+    /// Files end with a newline by default. The formatting of the template is overridden by the
+    /// `.editorconfig` settings, which are overridden by the formatting of the file itself.
+    /// Makefiles and YAML files are only reindented by the formatting of the file itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file relative to the project
+    /// * `content` - The rendered content of the file
+    /// * `project_path` - The root directory of the project
+    /// * `editorconfig` - The `.editorconfig` settings of the project
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The normalized content
+    pub fn format_file(
+        &self,
+        path: &Path,
+        content: &str,
+        project_path: &Path,
+        editorconfig: &EditorConfig,
+    ) -> String {
+        let file_formatting = self
+            .structure
+            .files
+            .iter()
+            .find(|file| file.path == path)
+            .map(|file| file.formatting.clone())
+            .unwrap_or_default();
+
+        self.format_content(path, content, project_path, editorconfig, &file_formatting)
+    }
+
+    /// Normalize a generated file with the formatting of the file itself
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file relative to the project
+    /// * `content` - The rendered content of the file
+    /// * `project_path` - The root directory of the project
+    /// * `editorconfig` - The `.editorconfig` settings of the project
+    /// * `file_formatting` - The formatting of the file, it overrides all other settings
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The normalized content
+    fn format_content(
+        &self,
+        path: &Path,
+        content: &str,
+        project_path: &Path,
+        editorconfig: &EditorConfig,
+        file_formatting: &Formatting,
+    ) -> String {
+        let mut formatting = Formatting {
+            final_newline: Some(true),
+            ..Default::default()
+        }
+        .merge(&self.formatting)
+        .merge(&editorconfig.formatting(&project_path.join(path)));
+        if has_significant_indentation(path) {
+            formatting.indent_style = None;
+            formatting.indent_size = None;
+        }
+
+        formatting.merge(file_formatting).apply(content)
+    }
+
+    /// Check if the template ships its own README file
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether one of the template files is a README
+    pub fn has_readme(&self) -> bool {
+        self.structure.files.iter().any(|file| {
+            file.path
+                .file_stem()
+                .is_some_and(|stem| stem.eq_ignore_ascii_case("readme"))
+        })
+    }
+
+    /// Generate a README from the template metadata and the given variables
+    ///
+    /// The README contains the project name, the description, the language, the start command,
+    /// the members of a workspace, the README section of the template and the license.
+    ///
+    /// # Arguments
+    ///
+    /// * `variables` - The variables of the project
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, Box<dyn std::error::Error>>` - The generated README
+    pub fn render_readme(
+        &self,
+        variables: &HashMap<String, String>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let variable = |name: &str| variables.get(name).cloned().unwrap_or_default();
+
+        let mut readme = vec![format!("# {}", variable("project_name")), String::new()];
+
+        let description = variable("description");
+        if !description.is_empty() {
+            readme.push(description);
+            readme.push(String::new());
+        }
+
+        if !self.start_command.is_empty() {
+            readme.push("## Getting Started".to_string());
+            readme.push(String::new());
+            readme.push(format!(
+                "This is a {} project. Start it with:",
+                variable("language")
+            ));
+            readme.push(String::new());
+            readme.push("```bash".to_string());
+            readme.push(self.render(vec![self.start_command.clone()], variables)?);
+            readme.push("```".to_string());
+            readme.push(String::new());
+        }
+
+        if !self.members.is_empty() {
+            readme.push("## Projects".to_string());
+            readme.push(String::new());
+            for member in &self.members {
+                readme.push(format!(
+                    "- [{}]({}) ({})",
+                    member.name,
+                    member.directory().display(),
+                    member.template
+                ));
+            }
+            readme.push(String::new());
+        }
+
+        if !self.readme.is_empty() {
+            readme.push(self.render(self.readme.clone(), variables)?);
+            readme.push(String::new());
+        }
+
+        let license = variable("license");
+        if !license.is_empty() {
+            readme.push("## License".to_string());
+            readme.push(String::new());
+            readme.push(format!(
+                "This project is licensed under the {} license. See [LICENSE](LICENSE) for details.",
+                license
+            ));
+            readme.push(String::new());
+        }
+
+        Ok(readme.join("\n"))
+    }
+
+    /// Render the task file of the project from the tasks of the template
+    ///
+    /// # Arguments
+    ///
+    /// * `variables` - The variables of the project
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<String>, Box<dyn std::error::Error>>` - The task file, `None` if the template has no tasks
+    pub fn render_tasks(
+        &self,
+        variables: &HashMap<String, String>,
+    ) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        if self.tasks.is_empty() {
+            return Ok(None);
+        }
+
+        let mut tasks = BTreeMap::new();
+        for (name, command) in &self.tasks {
+            tasks.insert(name.clone(), self.render(vec![command.clone()], variables)?);
+        }
+
+        Ok(Some(TaskFile { tasks }.render()?))
+    }
+
+    /// Render the content with the given variables
+    ///
+    /// Every `{{name}}` placeholder is replaced by the value of the variable `name` in a single
+    /// pass, so placeholders inside the inserted values are kept as they are. Placeholders of
+    /// unknown variables are left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The content lines to render
+    /// * `variables` - The variables to fill in (e.g. project_name, license)
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, Box<dyn std::error::Error>>` - The rendered content
+    pub fn render(
+        &self,
+        content: Vec<String>,
+        variables: &HashMap<String, String>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let content = content.join("\n");
+        let mut rendered = String::with_capacity(content.len());
 
-        content = content.replace("{{project_name}}", &project_name);
+        let mut rest = content.as_str();
+        while let Some(start) = rest.find("{{") {
+            rendered.push_str(&rest[..start]);
+            let placeholder = &rest[start..];
+            let Some(end) = placeholder[2..].find("}}") else {
+                rest = placeholder;
+                break;
+            };
 
-        Ok(content)
+            match variables.get(&placeholder[2..end + 2]) {
+                Some(value) => {
+                    rendered.push_str(value);
+                    rest = &placeholder[end + 4..];
+                }
+                None => {
+                    rendered.push_str("{{");
+                    rest = &placeholder[2..];
+                }
+            }
+        }
+        rendered.push_str(rest);
+
+        Ok(rendered)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::create_temp_dir;
+
+    fn rust_template() -> Template {
+        Template::load_template(
+            "rust".to_string(),
+            &Config::new(Config::bundled_template_dir()),
+        )
+        .unwrap()
+    }
 
     #[test]
     fn test_load_template() {
-        let template = Template::load_template("rust".to_string()).unwrap();
+        let dir = create_temp_dir("scaffer_test_load_template").unwrap();
+        let config = Config::new(dir.clone());
 
+        let template = Template::load_template("rust".to_string(), &config).unwrap();
         assert_eq!(template.start_command, "cargo run".to_string());
         assert_eq!(template.structure.directories.len(), 1);
         assert_eq!(template.structure.files.len(), 2);
+
+        assert_eq!(
+            Template::load_template("brainfuck".to_string(), &config)
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "No template found for brainfuck, expected {}",
+                dir.join("brainfuck.json").display()
+            )
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 
     #[test]
     fn test_parse_project_name() {
-        let template = Template::load_template("rust".to_string()).unwrap();
+        let template = rust_template();
         let content = vec!["{{project_name}}".to_string()];
 
         let parsed_content = template
@@ -91,4 +599,220 @@ mod tests {
 
         assert_eq!(parsed_content, "test".to_string());
     }
+
+    #[test]
+    fn test_render_tasks() {
+        let mut template = rust_template();
+        let variables = HashMap::from([("project_name".to_string(), "demo".to_string())]);
+
+        template.tasks = BTreeMap::from([
+            ("build".to_string(), "cargo build".to_string()),
+            (
+                "run".to_string(),
+                "cargo run --bin {{project_name}}".to_string(),
+            ),
+        ]);
+        let tasks = template.render_tasks(&variables).unwrap().unwrap();
+        assert!(
+            tasks.ends_with("[tasks]\nbuild = \"cargo build\"\nrun = \"cargo run --bin demo\"\n")
+        );
+
+        template.tasks.clear();
+        assert!(template.render_tasks(&variables).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_render_readme() {
+        let mut template = rust_template();
+        template.start_command = "cargo run --bin {{project_name}}".to_string();
+        template.readme = vec![
+            "## Notes".to_string(),
+            "Made for {{project_name}}.".to_string(),
+        ];
+        let variables = HashMap::from([
+            ("project_name".to_string(), "demo".to_string()),
+            ("description".to_string(), "A demo project".to_string()),
+            ("language".to_string(), "Rust".to_string()),
+            ("license".to_string(), "MIT".to_string()),
+        ]);
+
+        let readme = template.render_readme(&variables).unwrap();
+
+        assert!(readme.starts_with("# demo\n\nA demo project\n"));
+        assert!(readme.contains("This is a Rust project."));
+        assert!(readme.contains("```bash\ncargo run --bin demo\n```"));
+        assert!(readme.contains("## Notes\nMade for demo."));
+        assert!(readme.contains("licensed under the MIT license"));
+        assert!(!template.has_readme());
+    }
+
+    #[test]
+    fn test_format_file() {
+        let dir = create_temp_dir("scaffer_test_format_file").unwrap();
+        let template: Template = serde_json::from_str(
+            r#"{
+                "structure": {
+                    "directories": [],
+                    "files": [
+                        { "path": ".editorconfig", "content": ["[*.rs]", "indent_style = tab"] },
+                        { "path": "Makefile", "content": ["all:", "    cc main.c"], "formatting": { "indent_style": "tab", "indent_size": 4 } },
+                        { "path": "main.bat", "content": ["@echo off"], "formatting": { "line_ending": "crlf" } }
+                    ]
+                },
+                "start_command": "make",
+                "formatting": { "indent_style": "space", "indent_size": 2 }
+            }"#,
+        )
+        .unwrap();
+        let editorconfig = template.editorconfig(&dir, &HashMap::new()).unwrap();
+        let format = |path: &str, content: &str| {
+            template.format_file(Path::new(path), content, &dir, &editorconfig)
+        };
+
+        assert_eq!(
+            format("Makefile", "all:\n    cc main.c"),
+            "all:\n\tcc main.c\n"
+        );
+        assert_eq!(format("main.bat", "@echo off"), "@echo off\r\n");
+        assert_eq!(
+            format("src/main.rs", "fn main() {\n  run();\n}"),
+            "fn main() {\n\trun();\n}\n"
+        );
+        assert_eq!(
+            format("README.md", "# demo\n\t- item"),
+            "# demo\n  - item\n"
+        );
+
+        // Tab-significant files keep their indentation unless the file itself sets one
+        assert_eq!(
+            format("build/rules.mk", "all:\n\tcc main.c"),
+            "all:\n\tcc main.c\n"
+        );
+        assert_eq!(
+            format(".github/ci.yml", "jobs:\n    build:"),
+            "jobs:\n    build:\n"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_run_generator() {
+        let dir = create_temp_dir("scaffer_test_run_generator").unwrap();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/lib.rs"), "mod existing;").unwrap();
+
+        let template = rust_template();
+        let generator: Generator = serde_json::from_str(
+            r#"{
+                "name": "module",
+                "files": [{ "path": "src/{{name}}.rs", "content": ["pub fn {{name}}() {}"] }],
+                "append": [{ "path": "src/lib.rs", "content": ["mod {{name}};"] }]
+            }"#,
+        )
+        .unwrap();
+        let variables = HashMap::from([("name".to_string(), "parser".to_string())]);
+
+        let generated = template
+            .run_generator(&generator, &dir, &variables, false)
+            .unwrap();
+        assert_eq!(
+            generated,
+            vec![
+                GeneratedFile::Created(dir.join("src/parser.rs")),
+                GeneratedFile::Appended(dir.join("src/lib.rs")),
+            ]
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("src/lib.rs")).unwrap(),
+            "mod existing;\nmod parser;\n"
+        );
+        assert!(template
+            .run_generator(&generator, &dir, &variables, false)
+            .is_err());
+
+        let generated = template
+            .run_generator(&generator, &dir, &variables, true)
+            .unwrap();
+        assert_eq!(
+            generated[1],
+            GeneratedFile::Unchanged(dir.join("src/lib.rs"))
+        );
+
+        // Generated files are formatted like the files of a new project
+        std::fs::write(
+            dir.join(".editorconfig"),
+            "root = true\n\n[*.rs]\nindent_style = tab\n",
+        )
+        .unwrap();
+        let generator: Generator = serde_json::from_str(
+            r#"{
+                "name": "module",
+                "files": [{ "path": "src/{{name}}.rs", "content": ["fn {{name}}() {", "    run();", "}"] }]
+            }"#,
+        )
+        .unwrap();
+        template
+            .run_generator(&generator, &dir, &variables, true)
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.join("src/parser.rs")).unwrap(),
+            "fn parser() {\n\trun();\n}\n"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parse_member() {
+        let member: Member = "go:backend".parse().unwrap();
+        assert_eq!(member.template, "go");
+        assert_eq!(member.directory(), PathBuf::from("backend"));
+
+        let member = Member {
+            path: Some(PathBuf::from("services")),
+            ..member
+        };
+        assert_eq!(member.directory(), PathBuf::from("services/backend"));
+
+        assert!("go".parse::<Member>().is_err());
+        assert!(":backend".parse::<Member>().is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let template = rust_template();
+        let content = vec![
+            "name = \"{{project_name}}\"".to_string(),
+            "license = \"{{license}}\"".to_string(),
+        ];
+        let variables = HashMap::from([
+            ("project_name".to_string(), "demo".to_string()),
+            ("license".to_string(), "MIT".to_string()),
+        ]);
+
+        let rendered = template.render(content, &variables).unwrap();
+
+        assert_eq!(rendered, "name = \"demo\"\nlicense = \"MIT\"");
+
+        // Values are inserted as they are, even if they contain placeholders themselves
+        let variables = HashMap::from([
+            ("project_name".to_string(), "demo".to_string()),
+            (
+                "description".to_string(),
+                "{{project_name}} uses {{license}}".to_string(),
+            ),
+        ]);
+        for _ in 0..10 {
+            assert_eq!(
+                template
+                    .render(
+                        vec!["# {{project_name}}: {{description}} {{unknown}} {{".to_string()],
+                        &variables
+                    )
+                    .unwrap(),
+                "# demo: {{project_name}} uses {{license}} {{unknown}} {{"
+            );
+        }
+    }
 }
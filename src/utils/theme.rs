@@ -0,0 +1,505 @@
+use std::{
+    collections::HashSet,
+    sync::{Mutex, OnceLock},
+};
+
+use colored::{ColoredString, Colorize};
+use inquire::ui::{Attributes, Color, RenderConfig, StyleSheet, Styled};
+use serde::{Deserialize, Serialize};
+
+use super::Config;
+
+/// The names of the built-in themes
+pub const BUILTIN_THEMES: &[&str] = &["default", "minimal", "high-contrast", "no-emoji"];
+
+/// A color of the theme palette
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ThemeColor {
+    None,
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+impl ThemeColor {
+    /// The color for the `colored` output
+    ///
+    /// # Returns
+    ///
+    /// * `Option<colored::Color>` - The color, `None` for the default color of the terminal
+    fn output_color(self) -> Option<colored::Color> {
+        Some(match self {
+            ThemeColor::None => return None,
+            ThemeColor::Black => colored::Color::Black,
+            ThemeColor::Red => colored::Color::Red,
+            ThemeColor::Green => colored::Color::Green,
+            ThemeColor::Yellow => colored::Color::Yellow,
+            ThemeColor::Blue => colored::Color::Blue,
+            ThemeColor::Magenta => colored::Color::Magenta,
+            ThemeColor::Cyan => colored::Color::Cyan,
+            ThemeColor::White => colored::Color::White,
+            ThemeColor::BrightBlack => colored::Color::BrightBlack,
+            ThemeColor::BrightRed => colored::Color::BrightRed,
+            ThemeColor::BrightGreen => colored::Color::BrightGreen,
+            ThemeColor::BrightYellow => colored::Color::BrightYellow,
+            ThemeColor::BrightBlue => colored::Color::BrightBlue,
+            ThemeColor::BrightMagenta => colored::Color::BrightMagenta,
+            ThemeColor::BrightCyan => colored::Color::BrightCyan,
+            ThemeColor::BrightWhite => colored::Color::BrightWhite,
+        })
+    }
+
+    /// The color for the `inquire` prompts
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Color>` - The color, `None` for the default color of the terminal
+    fn prompt_color(self) -> Option<Color> {
+        Some(match self {
+            ThemeColor::None => return None,
+            ThemeColor::Black => Color::Black,
+            ThemeColor::Red => Color::DarkRed,
+            ThemeColor::Green => Color::DarkGreen,
+            ThemeColor::Yellow => Color::DarkYellow,
+            ThemeColor::Blue => Color::DarkBlue,
+            ThemeColor::Magenta => Color::DarkMagenta,
+            ThemeColor::Cyan => Color::DarkCyan,
+            ThemeColor::White => Color::Grey,
+            ThemeColor::BrightBlack => Color::DarkGrey,
+            ThemeColor::BrightRed => Color::LightRed,
+            ThemeColor::BrightGreen => Color::LightGreen,
+            ThemeColor::BrightYellow => Color::LightYellow,
+            ThemeColor::BrightBlue => Color::LightBlue,
+            ThemeColor::BrightMagenta => Color::LightMagenta,
+            ThemeColor::BrightCyan => Color::LightCyan,
+            ThemeColor::BrightWhite => Color::White,
+        })
+    }
+}
+
+/// The role of a text in the output, mapped to a color of the palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// Steps and list bullets
+    Accent,
+    /// Successful actions and additions
+    Success,
+    /// Warnings and skipped actions
+    Warning,
+    /// Failures and removals
+    Error,
+    /// Headings of the summary, shown in bold if the theme is bold
+    Heading,
+    /// Answers and highlighted options of the prompts
+    Highlight,
+    /// Details and placeholders
+    Muted,
+}
+
+/// The symbols of a theme
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Symbols {
+    pub branch: String,
+    pub step: String,
+    pub success: String,
+    pub unchanged: String,
+    pub skipped: String,
+    pub warning: String,
+    pub failure: String,
+    pub item: String,
+    pub done: String,
+    pub prompt: String,
+    pub answered: String,
+    pub pointer: String,
+}
+
+/// The colors and symbols of the output and the prompts
+///
+/// The theme is selected with the `theme` setting of the config file. Besides the built-in
+/// themes, themes can be defined in the `[themes.<name>]` sections of the config file, they
+/// extend a built-in theme (`default` unless set with `extends`).
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Theme {
+    pub colors: bool,
+    pub bold: bool,
+    pub accent: ThemeColor,
+    pub success: ThemeColor,
+    pub warning: ThemeColor,
+    pub error: ThemeColor,
+    pub heading: ThemeColor,
+    pub highlight: ThemeColor,
+    pub muted: ThemeColor,
+    pub symbols: Symbols,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            colors: true,
+            bold: true,
+            accent: ThemeColor::BrightBlue,
+            success: ThemeColor::BrightGreen,
+            warning: ThemeColor::BrightYellow,
+            error: ThemeColor::BrightRed,
+            heading: ThemeColor::BrightYellow,
+            highlight: ThemeColor::BrightCyan,
+            muted: ThemeColor::BrightBlack,
+            symbols: Symbols {
+                branch: "└─".to_string(),
+                step: "►".to_string(),
+                success: "✓".to_string(),
+                unchanged: "↷".to_string(),
+                skipped: "↷".to_string(),
+                warning: "!".to_string(),
+                failure: "✗".to_string(),
+                item: "→".to_string(),
+                done: "🎉".to_string(),
+                prompt: "?".to_string(),
+                answered: ">".to_string(),
+                pointer: ">".to_string(),
+            },
+        }
+    }
+}
+
+impl Theme {
+    /// Get a built-in theme by its name
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the theme (e.g. default, no-emoji)
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Theme>` - The theme, `None` if there is no built-in theme with the name
+    pub fn builtin(name: &str) -> Option<Self> {
+        let default = Self::default();
+        let ascii = |symbols: &Symbols| Symbols {
+            branch: "`-".to_string(),
+            step: ">".to_string(),
+            success: "+".to_string(),
+            unchanged: "=".to_string(),
+            skipped: "~".to_string(),
+            warning: "!".to_string(),
+            failure: "x".to_string(),
+            item: "->".to_string(),
+            done: String::new(),
+            ..symbols.clone()
+        };
+
+        match name {
+            "default" => Some(default),
+            "minimal" => Some(Self {
+                bold: false,
+                accent: ThemeColor::None,
+                success: ThemeColor::Green,
+                warning: ThemeColor::Yellow,
+                error: ThemeColor::Red,
+                heading: ThemeColor::None,
+                highlight: ThemeColor::None,
+                symbols: Symbols {
+                    branch: String::new(),
+                    step: String::new(),
+                    success: String::new(),
+                    unchanged: String::new(),
+                    skipped: String::new(),
+                    item: "-".to_string(),
+                    done: String::new(),
+                    ..ascii(&default.symbols)
+                },
+                ..default
+            }),
+            "high-contrast" => Some(Self {
+                accent: ThemeColor::BrightCyan,
+                heading: ThemeColor::BrightWhite,
+                highlight: ThemeColor::BrightWhite,
+                muted: ThemeColor::White,
+                ..default
+            }),
+            "no-emoji" => Some(Self {
+                symbols: ascii(&default.symbols),
+                ..default
+            }),
+            _ => None,
+        }
+    }
+
+    /// Load the theme selected in the configuration
+    ///
+    /// Colors are disabled if the `NO_COLOR` environment variable is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration object
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Theme, Box<dyn std::error::Error>>` - The selected theme
+    ///
+    /// # Errors
+    ///
+    /// * If there is no theme with the configured name
+    /// * If a user-defined theme extends an unknown theme or has invalid settings
+    pub fn from_config(config: &Config) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut theme = match config.themes.get(&config.theme) {
+            Some(settings) => Self::extend(settings)?,
+            None => Self::builtin(&config.theme).ok_or_else(|| {
+                let mut names: Vec<&str> = BUILTIN_THEMES.to_vec();
+                names.extend(config.themes.keys().map(String::as_str));
+                format!(
+                    "Unknown theme `{}`. Available themes: {}",
+                    config.theme,
+                    names.join(", ")
+                )
+            })?,
+        };
+
+        if std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            theme.colors = false;
+        }
+
+        Ok(theme)
+    }
+
+    /// Build a user-defined theme from the settings of its config section
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - The settings of the theme, overriding the ones of the extended theme
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Theme, Box<dyn std::error::Error>>` - The theme
+    ///
+    /// # Errors
+    ///
+    /// * If the extended theme is not a built-in theme
+    /// * If a setting is invalid (e.g. an unknown color)
+    fn extend(settings: &toml::Table) -> Result<Self, Box<dyn std::error::Error>> {
+        let base = settings
+            .get("extends")
+            .and_then(toml::Value::as_str)
+            .unwrap_or("default");
+        let base = Self::builtin(base).ok_or_else(|| {
+            format!(
+                "Themes can only extend the built-in themes ({}), not `{}`",
+                BUILTIN_THEMES.join(", "),
+                base
+            )
+        })?;
+
+        let mut theme = toml::Table::try_from(base)?;
+        for (key, value) in settings.iter().filter(|(key, _)| *key != "extends") {
+            match (theme.get_mut(key), value) {
+                (Some(toml::Value::Table(symbols)), toml::Value::Table(overrides)) => {
+                    symbols.extend(overrides.clone())
+                }
+                _ => {
+                    theme.insert(key.clone(), value.clone());
+                }
+            }
+        }
+
+        Ok(theme.try_into()?)
+    }
+
+    /// Color a text according to its role
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to color
+    /// * `style` - The role of the text
+    ///
+    /// # Returns
+    ///
+    /// * `ColoredString` - The colored text, unchanged if the theme has no colors
+    pub fn paint(&self, text: &str, style: Style) -> ColoredString {
+        if !self.colors {
+            return text.normal();
+        }
+
+        let painted = match self.color(style).output_color() {
+            Some(color) => text.color(color),
+            None => text.normal(),
+        };
+        match style {
+            Style::Heading if self.bold => painted.bold(),
+            _ => painted,
+        }
+    }
+
+    /// The style sheet of a text of the `inquire` prompts
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - The role of the text
+    ///
+    /// # Returns
+    ///
+    /// * `StyleSheet` - The style sheet, empty if the theme has no colors
+    pub fn prompt_style(&self, style: Style) -> StyleSheet {
+        match self.color(style).prompt_color().filter(|_| self.colors) {
+            Some(color) => StyleSheet::new().with_fg(color),
+            None => StyleSheet::empty(),
+        }
+    }
+
+    /// The color of the palette for a role
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - The role of a text
+    ///
+    /// # Returns
+    ///
+    /// * `ThemeColor` - The color of the role
+    fn color(&self, style: Style) -> ThemeColor {
+        match style {
+            Style::Accent => self.accent,
+            Style::Success => self.success,
+            Style::Warning => self.warning,
+            Style::Error => self.error,
+            Style::Heading => self.heading,
+            Style::Highlight => self.highlight,
+            Style::Muted => self.muted,
+        }
+    }
+
+    /// The render configuration of the `inquire` prompts
+    ///
+    /// # Returns
+    ///
+    /// * `RenderConfig<'static>` - The render configuration with the colors and symbols of the theme
+    pub fn render_config(&self) -> RenderConfig<'static> {
+        let style = |style: Style| self.prompt_style(style);
+
+        let mut config = RenderConfig::empty()
+            .with_prompt_prefix(
+                Styled::new(static_symbol(&self.symbols.prompt))
+                    .with_style_sheet(style(Style::Success)),
+            )
+            .with_answered_prompt_prefix(
+                Styled::new(static_symbol(&self.symbols.answered))
+                    .with_style_sheet(style(Style::Success)),
+            )
+            .with_highlighted_option_prefix(
+                Styled::new(static_symbol(&self.symbols.pointer))
+                    .with_style_sheet(style(Style::Highlight)),
+            )
+            .with_help_message(style(Style::Highlight))
+            .with_answer(style(Style::Highlight))
+            .with_selected_option(Some(style(Style::Highlight)))
+            .with_canceled_prompt_indicator(
+                Styled::new("<canceled>").with_style_sheet(style(Style::Error)),
+            )
+            .with_editor_prompt(style(Style::Highlight));
+        config.placeholder = style(Style::Muted);
+        config.error_message = config
+            .error_message
+            .with_prefix(Styled::new("#").with_style_sheet(style(Style::Error)))
+            .with_message(style(Style::Error));
+        if self.bold && self.colors {
+            config.prompt = StyleSheet::new().with_attr(Attributes::BOLD);
+        }
+
+        config
+    }
+}
+
+/// Get a static reference to a prompt symbol
+///
+/// The render configuration of `inquire` only borrows its symbols, so every distinct symbol is
+/// stored once for the lifetime of the process instead of once per render configuration.
+///
+/// # Arguments
+///
+/// * `symbol` - The symbol of the theme
+///
+/// # Returns
+///
+/// * `&'static str` - The stored symbol
+fn static_symbol(symbol: &str) -> &'static str {
+    static SYMBOLS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+    let mut symbols = SYMBOLS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    if let Some(stored) = symbols.get(symbol) {
+        return stored;
+    }
+
+    let stored: &'static str = symbol.to_string().leak();
+    symbols.insert(stored);
+    stored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_themes() {
+        for name in BUILTIN_THEMES {
+            assert!(Theme::builtin(name).is_some(), "{}", name);
+        }
+        assert!(Theme::builtin("unknown").is_none());
+
+        let theme = Theme::builtin("no-emoji").unwrap();
+        assert!([
+            &theme.symbols.step,
+            &theme.symbols.success,
+            &theme.symbols.failure,
+            &theme.symbols.done
+        ]
+        .iter()
+        .all(|symbol| symbol.is_ascii()));
+    }
+
+    #[test]
+    fn test_user_theme() {
+        let settings: toml::Table = toml::from_str(
+            r#"
+                extends = "no-emoji"
+                accent = "magenta"
+                symbols = { success = "ok" }
+            "#,
+        )
+        .unwrap();
+
+        let theme = Theme::extend(&settings).unwrap();
+        assert_eq!(theme.accent, ThemeColor::Magenta);
+        assert_eq!(theme.symbols.success, "ok");
+        assert!(std::ptr::eq(static_symbol("ok"), static_symbol("ok")));
+        assert_eq!(theme.symbols.failure, "x");
+
+        let settings: toml::Table = toml::from_str(r#"accent = "purple""#).unwrap();
+        assert!(Theme::extend(&settings).is_err());
+        let settings: toml::Table = toml::from_str(r#"extends = "mine""#).unwrap();
+        assert!(Theme::extend(&settings).is_err());
+    }
+
+    #[test]
+    fn test_paint_without_colors() {
+        let theme = Theme {
+            colors: false,
+            ..Theme::default()
+        };
+
+        assert_eq!(theme.paint("done", Style::Success).to_string(), "done");
+    }
+}